@@ -0,0 +1,293 @@
+use crate::reader;
+use csv::WriterBuilder;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Entities compared between releases, by the name of their output file
+const KINDS: [&str; 6] = [
+    "variant",
+    "case",
+    "association",
+    "disease",
+    "submission",
+    "gene",
+];
+
+/// Properties that differ between conversions of the same row, e.g. with --provenance
+const IGNORED: [&str; 2] = ["was_derived_from", "source_lines"];
+
+/// Entities of a release by kind and ID, each a map of its properties to canonical JSON
+#[derive(Debug, Default)]
+pub struct Release {
+    entities: BTreeMap<&'static str, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeType {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Change {
+    kind: &'static str,
+    id: String,
+    change: ChangeType,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new: Option<String>,
+}
+
+/// Numbers of changes of a kind of entity, and of the changed entities by property
+#[derive(Debug, Default, Serialize)]
+pub struct Summary {
+    added: usize,
+    removed: usize,
+    changed: usize,
+    fields: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diff {
+    summary: BTreeMap<&'static str, Summary>,
+    changes: Vec<Change>,
+}
+
+impl Display for ChangeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeType::Added => write!(f, "added"),
+            ChangeType::Removed => write!(f, "removed"),
+            ChangeType::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+/// Value with the elements of arrays sorted, as sets are serialized in arbitrary order
+fn canonical(value: &Value) -> Value {
+    match value {
+        Value::Array(x) => {
+            let mut items: Vec<Value> = x.iter().map(canonical).collect();
+            items.sort_by_cached_key(|x| x.to_string());
+            Value::Array(items)
+        }
+        Value::Object(x) => Value::Object(
+            x.iter()
+                .map(|(k, v)| (k.to_string(), canonical(v)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Strings as is, anything else as canonical JSON
+fn field_value(value: &Value) -> String {
+    match value {
+        Value::String(x) => x.to_string(),
+        _ => canonical(value).to_string(),
+    }
+}
+
+impl Release {
+    /// Read the `mgend_*.jsonld.gz` files of an output directory
+    pub fn from_directory<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let mut release = Release::default();
+
+        for kind in KINDS {
+            let mut file = PathBuf::from(path.as_ref());
+            file.push(format!("mgend_{}.jsonld.gz", kind));
+            let err = |e: &dyn std::fmt::Display| format!("{}: {}", file.to_string_lossy(), e);
+
+            if !file.exists() {
+                let mut ttl = PathBuf::from(path.as_ref());
+                ttl.push(format!("mgend_{}.ttl.gz", kind));
+                if ttl.exists() {
+                    Err(err(&"not found, convert with --format jsonld to compare"))?
+                }
+                // e.g. no associations before they were split from cases
+                continue;
+            }
+
+            let mut buf = String::new();
+            reader::open(&file)
+                .and_then(|mut x| x.read_to_string(&mut buf))
+                .map_err(|e| err(&e))?;
+            let json: Value = serde_json::from_str(&buf).map_err(|e| err(&e))?;
+
+            let entities = release.entities.entry(kind).or_default();
+            for item in json
+                .get("@graph")
+                .and_then(|x| x.as_array())
+                .ok_or_else(|| err(&"expected a @graph array"))?
+            {
+                let (id, fields) = match (item.get("id").and_then(|x| x.as_str()), item) {
+                    (Some(id), Value::Object(x)) => (id, x),
+                    _ => Err(err(&"expected objects with an id"))?,
+                };
+                entities.insert(
+                    id.to_string(),
+                    fields
+                        .iter()
+                        .filter(|(k, _)| *k != "id" && !IGNORED.contains(&k.as_str()))
+                        .map(|(k, v)| (k.to_string(), field_value(v)))
+                        .collect(),
+                );
+            }
+        }
+
+        Ok(release)
+    }
+}
+
+impl Diff {
+    pub fn new(old: &Release, new: &Release) -> Self {
+        let mut summary: BTreeMap<&'static str, Summary> = BTreeMap::new();
+        let mut changes = Vec::new();
+        let empty = BTreeMap::new();
+
+        for kind in KINDS {
+            let s = summary.entry(kind).or_default();
+            let old = old.entities.get(kind).unwrap_or(&empty);
+            let new = new.entities.get(kind).unwrap_or(&empty);
+
+            for (id, x) in old {
+                match new.get(id) {
+                    None => {
+                        s.removed += 1;
+                        changes.push(Change {
+                            kind,
+                            id: id.to_string(),
+                            change: ChangeType::Removed,
+                            fields: Vec::new(),
+                        });
+                    }
+                    Some(y) if x != y => {
+                        let mut fields: Vec<&String> = x.keys().chain(y.keys()).collect();
+                        fields.sort();
+                        fields.dedup();
+
+                        let fields: Vec<FieldChange> = fields
+                            .into_iter()
+                            .filter(|k| x.get(*k) != y.get(*k))
+                            .map(|k| FieldChange {
+                                field: k.to_string(),
+                                old: x.get(k).cloned(),
+                                new: y.get(k).cloned(),
+                            })
+                            .collect();
+                        for f in &fields {
+                            *s.fields.entry(f.field.clone()).or_default() += 1;
+                        }
+
+                        s.changed += 1;
+                        changes.push(Change {
+                            kind,
+                            id: id.to_string(),
+                            change: ChangeType::Changed,
+                            fields,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for id in new.keys().filter(|x| !old.contains_key(*x)) {
+                s.added += 1;
+                changes.push(Change {
+                    kind,
+                    id: id.to_string(),
+                    change: ChangeType::Added,
+                    fields: Vec::new(),
+                });
+            }
+        }
+
+        Diff { summary, changes }
+    }
+
+    /// Write mgend_diff.tsv with a line per added or removed entity and per changed property,
+    /// and mgend_diff.json with the summary and the changes
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = PathBuf::from(path.as_ref());
+        out.push("mgend_diff.tsv");
+        eprintln!("writing {:?}", &out);
+
+        let mut writer = WriterBuilder::new().delimiter(b'\t').from_path(&out)?;
+        writer.write_record(["kind", "id", "change", "field", "old", "new"])?;
+        for x in &self.changes {
+            let change = x.change.to_string();
+            if x.fields.is_empty() {
+                writer.write_record([x.kind, &x.id, &change, "", "", ""])?;
+            }
+            for f in &x.fields {
+                writer.write_record([
+                    x.kind,
+                    &x.id,
+                    &change,
+                    &f.field,
+                    f.old.as_deref().unwrap_or_default(),
+                    f.new.as_deref().unwrap_or_default(),
+                ])?;
+            }
+        }
+        writer.flush()?;
+
+        let mut out = PathBuf::from(path.as_ref());
+        out.push("mgend_diff.json");
+        eprintln!("writing {:?}", &out);
+
+        let mut writer = std::fs::File::create(&out)?;
+        writer.write_all(serde_json::to_string_pretty(self).unwrap().as_bytes())?;
+
+        Ok(())
+    }
+}
+
+/// e.g. `case: 2 added, 1 removed, 3 changed (case_significance 2, case_count_total 1)`
+impl Display for Diff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for kind in KINDS {
+            let s = match self.summary.get(kind) {
+                Some(x) => x,
+                None => continue,
+            };
+            write!(
+                f,
+                "{}: {} added, {} removed, {} changed",
+                kind, s.added, s.removed, s.changed
+            )?;
+
+            let mut fields: Vec<(&String, &usize)> = s.fields.iter().collect();
+            fields.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+            if !fields.is_empty() {
+                write!(
+                    f,
+                    " ({})",
+                    fields
+                        .iter()
+                        .map(|(k, n)| format!("{} {}", k, n))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -59,3 +59,46 @@ pub trait ToTurtle {
         Ok(())
     }
 }
+
+/// Number of triples of Turtle as written by `ToTurtle`, i.e. a statement per subject ending
+/// with ` .` and blank nodes in `[...]`
+pub fn count_triples(ttl: &str) -> usize {
+    let mut count = 0;
+    let mut chars = ttl.chars().peekable();
+    let mut prev = ' ';
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '<' => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            // `;` and `,` end a triple, `]` and ` .` the last triple of a predicate-object list
+            ';' | ',' | ']' => count += 1,
+            '.' if prev.is_whitespace() && chars.peek().is_none_or(|x| x.is_whitespace()) => {
+                count += 1
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+
+    count
+}
@@ -1,27 +1,55 @@
+// regex! caches the compiled pattern, so creating one in a loop is cheap
+#![allow(clippy::upper_case_acronyms, clippy::regex_creation_in_loops)]
+// for the json! literals of the larger JSON-LD contexts
+#![recursion_limit = "256"]
+
+mod diff;
 mod format;
 mod models;
+mod reader;
+mod report;
 
+use crate::diff::{Diff, Release};
 use crate::format::jsonld::JSONLD;
-use crate::format::turtle::ToTurtle;
+use crate::format::turtle::{self, ToTurtle};
+use crate::models::annotation::GeneModels;
+use crate::models::chromosome::Chromosome;
 use crate::models::context::Contexts;
+use crate::models::cytoband::CytoBands;
+use crate::models::hgnc::{Resolution, HGNC};
+use crate::models::infectious_agent::InfectiousAgents;
 use crate::models::input::Record;
 use crate::models::name_space::{NameSpace, NameSpaces, MGEND_DISEASE};
-use crate::models::output::disease::Disease;
-use crate::models::output::gene::Gene;
-use crate::models::output::submission::Submission;
+use crate::models::ontology::Ontologies;
+use crate::models::output::association::Association;
+use crate::models::output::cytoband::CytoBand;
+use crate::models::output::dataset::{Dataset, DatasetConfig, Source};
+use crate::models::output::disease::{Disease, DiseaseIds};
+use crate::models::output::gene::{Gene, GeneConcordance};
+use crate::models::output::organization::{Organization, RorMapping};
+use crate::models::output::person::Person;
+use crate::models::output::provenance::Provenance;
+use crate::models::output::submission::{self, Submission};
 use crate::models::output::variant::Variant;
+use crate::models::output::xref::XRefNode;
+use crate::models::output::XRef;
+use crate::models::prefix_map::{PrefixMap, PrefixMaps};
+use crate::models::reference::Reference;
+use crate::models::vocabulary::{Validation, Vocabularies};
+use crate::report::Report;
 use csv::ReaderBuilder;
-use flate2::bufread::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use models::input::Row;
-use models::output::case::Case;
+use models::output::case::{content, content_key, Case, CaseKeys};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsString;
 use std::fmt::Debug;
 use std::fs::File;
 use std::io;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 use strum::{Display, EnumString, EnumVariantNames, VariantNames};
@@ -42,6 +70,41 @@ pub enum Format {
     Turtle,
 }
 
+/// Steps applied to DiseaseName before minting disease IDs
+#[derive(Debug, Clone, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "snake_case")]
+pub enum Normalization {
+    /// Unicode NFKC, e.g. full-width to half-width characters
+    NFKC,
+    /// Trim and collapse runs of whitespace
+    Whitespace,
+    /// Unicode case folding, e.g. `ß` to `ss`
+    Case,
+}
+
+/// Predicate linking cross-reference IRIs to their alternate IRIs
+#[derive(Debug, Clone, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "snake_case")]
+pub enum AlternatePredicate {
+    /// owl:sameAs
+    SameAs,
+    /// skos:exactMatch
+    ExactMatch,
+}
+
+const DEFAULT_NORMALIZATION: &str = "nfkc,whitespace,case";
+const DEFAULT_ALTERNATE_PREDICATE: &str = "exact_match";
+
+#[derive(Debug, StructOpt)]
+#[structopt(after_help = "Without a subcommand, the arguments are those of `convert`")]
+#[allow(clippy::large_enum_variant)]
+enum Command {
+    /// Convert an input file to RDF
+    Convert(Options),
+    /// Compare two releases
+    Diff(DiffOptions),
+}
+
 #[derive(Debug, StructOpt)]
 struct Options {
     /// Assembly
@@ -60,6 +123,87 @@ struct Options {
     #[structopt(long, parse(from_os_str))]
     hgnc: PathBuf,
 
+    /// Path to withdrawn.txt to follow withdrawn and merged symbols to their current HGNC ID (wget ftp://ftp.ebi.ac.uk/pub/databases/genenames/new/tsv/withdrawn.txt)
+    #[structopt(long, parse(from_os_str))]
+    hgnc_withdrawn: Option<PathBuf>,
+
+    /// Path to gene annotation of the assembly to find the genes overlapping each variant [*.gtf | *.gff3, optionally gzipped] (e.g. GENCODE, RefSeq)
+    #[structopt(long, parse(from_os_str))]
+    annotation: Option<PathBuf>,
+
+    /// Path to uncompressed reference genome FASTA of the assembly to predict consequences on the transcripts of --annotation
+    #[structopt(long, parse(from_os_str), requires("annotation"))]
+    reference: Option<PathBuf>,
+
+    /// Path to UCSC cytoBand.txt of the assembly (wget https://hgdownload.soe.ucsc.edu/goldenPath/hg38/database/cytoBand.txt.gz)
+    #[structopt(long, parse(from_os_str))]
+    cytoband: Option<PathBuf>,
+
+    /// Normalization of disease names before minting disease IDs
+    #[structopt(
+        long,
+        possible_values(Normalization::VARIANTS),
+        use_delimiter(true),
+        default_value(DEFAULT_NORMALIZATION)
+    )]
+    disease_normalization: Vec<Normalization>,
+
+    /// Paths to MONDO, HPO or Orphanet ontology files to map disease names to [*.obo | *.owl, optionally gzipped] (wget http://purl.obolibrary.org/obo/mondo.obo)
+    #[structopt(long, parse(from_os_str))]
+    disease_ontology: Vec<PathBuf>,
+
+    /// Path to ICD-10 code list to validate CodeValue (e.g. icd10cm_codes_2024.txt)
+    #[structopt(long, parse(from_os_str))]
+    icd10: Option<PathBuf>,
+
+    /// Paths to MeSH descriptors or supplementary concepts in ASCII format to validate ConditionIDValue (e.g. d2024.bin, c2024.bin)
+    #[structopt(long, parse(from_os_str))]
+    mesh: Vec<PathBuf>,
+
+    /// Path to HPO to validate ConditionIDValue [*.obo | *.owl] (wget http://purl.obolibrary.org/obo/hp.obo)
+    #[structopt(long, parse(from_os_str))]
+    hpo: Option<PathBuf>,
+
+    /// Path to Orphanet Rare Disease Ontology to validate ConditionIDValue [*.obo | *.owl] (e.g. ORDO_en_4.4.owl)
+    #[structopt(long, parse(from_os_str))]
+    orphanet: Option<PathBuf>,
+
+    /// Path to prefix map overriding the IRIs of cross-references [*.json | *.tsv] (Bioregistry JSON, or prefix and URI format with $1 per line)
+    #[structopt(long, parse(from_os_str))]
+    prefix_map: Option<PathBuf>,
+
+    /// Paths to prefix maps of alternate IRIs to link cross-references to [*.json | *.tsv]
+    #[structopt(long, parse(from_os_str))]
+    alternate_prefix_map: Vec<PathBuf>,
+
+    /// Predicate linking cross-references to their alternate IRIs
+    #[structopt(
+        long,
+        possible_values(AlternatePredicate::VARIANTS),
+        default_value(DEFAULT_ALTERNATE_PREDICATE)
+    )]
+    alternate_predicate: AlternatePredicate,
+
+    /// Path to TSV of submitter institute names and ROR IDs to use as organization IDs
+    #[structopt(long, parse(from_os_str))]
+    ror: Option<PathBuf>,
+
+    /// Path to TSV of sub disease areas, NCBI Taxonomy IDs of their pathogens and optional patterns of the disease names naming them, replacing the built-in agents of the areas it gives (e.g. `Hepatitis<TAB>10407<TAB>hepatitis B|\bHBV\b`)
+    #[structopt(long, parse(from_os_str))]
+    infectious_agent: Option<PathBuf>,
+
+    /// Path to TSV of the title, description, license and publisher of the dataset described in mgend_dataset.ttl (e.g. `license<TAB>https://creativecommons.org/licenses/by/4.0/`)
+    #[structopt(long, parse(from_os_str))]
+    dataset_config: Option<PathBuf>,
+
+    /// Withhold age and sex counts smaller than this (at least 2), the counts they could be derived from, and the totals and denominators of their cases
+    #[structopt(long, parse(try_from_str = parse_min_cell_size))]
+    min_cell_size: Option<i32>,
+
+    /// Record the line number and hash of the row each case was converted from (without the hash with --min-cell-size), and the lines of the variants, diseases and submissions
+    #[structopt(long)]
+    provenance: bool,
+
     /// Process only one line
     #[structopt(long)]
     rehearsal: bool,
@@ -69,6 +213,83 @@ struct Options {
     input: PathBuf,
 }
 
+impl Options {
+    /// Options of a conversion with none of the optional arguments given
+    fn new(
+        assembly: Assembly,
+        format: Format,
+        hgnc: PathBuf,
+        directory: PathBuf,
+        input: PathBuf,
+    ) -> Self {
+        Options {
+            assembly,
+            directory,
+            format,
+            hgnc,
+            hgnc_withdrawn: None,
+            annotation: None,
+            reference: None,
+            cytoband: None,
+            disease_normalization: DEFAULT_NORMALIZATION
+                .split(',')
+                .filter_map(|x| x.parse().ok())
+                .collect(),
+            disease_ontology: Vec::new(),
+            icd10: None,
+            mesh: Vec::new(),
+            hpo: None,
+            orphanet: None,
+            prefix_map: None,
+            alternate_prefix_map: Vec::new(),
+            alternate_predicate: DEFAULT_ALTERNATE_PREDICATE
+                .parse()
+                .unwrap_or(AlternatePredicate::ExactMatch),
+            ror: None,
+            infectious_agent: None,
+            dataset_config: None,
+            min_cell_size: None,
+            provenance: false,
+            rehearsal: false,
+            input,
+        }
+    }
+}
+
+/// A minimum cell size of 1 or less would withhold nothing
+fn parse_min_cell_size(s: &str) -> Result<i32, String> {
+    match s.parse::<i32>() {
+        Ok(k) if k >= 2 => Ok(k),
+        Ok(k) => Err(format!("must be at least 2: {}", k)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Compare two releases, converting input files to JSON-LD in <directory>/old and <directory>/new.
+/// Output directories given as a release must have been converted with `--format jsonld`.
+#[derive(Debug, StructOpt)]
+struct DiffOptions {
+    /// Assembly, to convert input files
+    #[structopt(long, possible_values(Assembly::VARIANTS))]
+    assembly: Option<Assembly>,
+
+    /// Path to hgnc_complete_set.txt, to convert input files
+    #[structopt(long, parse(from_os_str))]
+    hgnc: Option<PathBuf>,
+
+    /// Path to output directory of mgend_diff.tsv and mgend_diff.json
+    #[structopt(long, parse(from_os_str))]
+    directory: PathBuf,
+
+    /// Path to input file or output directory of the old release converted with --format jsonld
+    #[structopt(parse(from_os_str))]
+    old: PathBuf,
+
+    /// Path to input file or output directory of the new release converted with --format jsonld
+    #[structopt(parse(from_os_str))]
+    new: PathBuf,
+}
+
 macro_rules! write_jsonld {
     ($output:expr, $filename:expr, $context:expr, $graph:expr) => {
         write_jsonld(&$output, $filename, &JSONLD::new(&$context, &$graph))
@@ -76,149 +297,923 @@ macro_rules! write_jsonld {
 }
 
 fn main() -> io::Result<()> {
-    let option = Options::from_args();
-
-    let f = File::open(&option.input).unwrap();
-    let r: Box<dyn io::Read> = match option.input.extension() {
-        Some(ext) if ext == "gz" => Box::new(GzDecoder::new(BufReader::new(f))),
-        _ => Box::new(BufReader::new(f)),
+    // arguments without a subcommand are converted as before the subcommands
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let command = match args.get(1).and_then(|x| x.to_str()) {
+        Some("convert" | "diff" | "help" | "-h" | "--help" | "-V" | "--version") => {
+            Command::from_iter(args)
+        }
+        _ => Command::Convert(Options::from_iter(args)),
     };
 
+    match command {
+        Command::Convert(option) => convert(option),
+        Command::Diff(option) => diff(option),
+    }
+}
+
+fn convert(option: Options) -> io::Result<()> {
     let mut reader = ReaderBuilder::new()
         .has_headers(true)
         .delimiter(b'\t')
-        .from_reader(r);
+        .from_reader(reader::open(&option.input)?);
 
     let mut cases = Vec::new();
+    let mut associations = Vec::new();
     let mut submissions = HashMap::new();
     let mut genes = HashMap::new();
-    let mut diseases = HashMap::new();
-    let mut variants = HashMap::new();
+    let mut organizations = HashMap::new();
+    let mut persons = HashMap::new();
+    let mut diseases: HashMap<String, Disease> = HashMap::new();
+    let mut variants: HashMap<String, Variant> = HashMap::new();
+    let mut xref_nodes = BTreeMap::new();
 
-    for result in reader.deserialize::<Row>() {
-        let row = result?;
-        let record = Record::new(&option.assembly, &row);
+    let hgnc =
+        HGNC::from_path(&option.hgnc, option.hgnc_withdrawn.as_ref()).map_err(io::Error::other)?;
 
-        let case = Case::from(&record);
+    let cytobands = match option.cytoband {
+        Some(ref path) => {
+            Some(CytoBands::from_path(path, &option.assembly).map_err(io::Error::other)?)
+        }
+        None => None,
+    };
+
+    let gene_models = match option.annotation {
+        Some(ref path) => {
+            Some(GeneModels::from_path(path, &option.assembly).map_err(io::Error::other)?)
+        }
+        None => None,
+    };
+
+    let reference = match option.reference {
+        Some(ref path) => {
+            Some(Reference::from_path(path, &option.assembly).map_err(io::Error::other)?)
+        }
+        None => None,
+    };
+
+    let ontologies = if option.disease_ontology.is_empty() {
+        None
+    } else {
+        let ontologies =
+            Ontologies::from_paths(&option.disease_ontology, &option.disease_normalization)
+                .map_err(io::Error::other)?;
+        eprintln!("loaded {} ontology terms", ontologies.len());
+        Some(ontologies)
+    };
+
+    let prefix_maps = PrefixMaps {
+        primary: match option.prefix_map {
+            Some(ref path) => {
+                PrefixMap::defaults().merge(PrefixMap::from_path(path).map_err(io::Error::other)?)
+            }
+            None => PrefixMap::defaults(),
+        },
+        alternates: option
+            .alternate_prefix_map
+            .iter()
+            .map(PrefixMap::from_path)
+            .collect::<Result<Vec<PrefixMap>, String>>()
+            .map_err(io::Error::other)?,
+        predicate: option.alternate_predicate.clone(),
+    };
+    let infectious_agents = match option.infectious_agent {
+        Some(ref path) => InfectiousAgents::default()
+            .merge(InfectiousAgents::from_path(path).map_err(io::Error::other)?),
+        None => InfectiousAgents::default(),
+    };
+    let dataset_config = match option.dataset_config {
+        Some(ref path) => DatasetConfig::from_path(path).map_err(io::Error::other)?,
+        None => DatasetConfig::default(),
+    };
+    let source = Source::from_path(&option.input)?;
+
+    let ror = match option.ror {
+        Some(ref path) => RorMapping::from_path(path).map_err(io::Error::other)?,
+        None => RorMapping::default(),
+    };
+    let mut invalid_ror_ids = Report::new("mgend_report_ror.tsv", &["name", "id"]);
+    for line in ror.invalid() {
+        let (name, id) = line.split_once('\t').unwrap_or((line, ""));
+        invalid_ror_ids.add(&[name, id]);
+    }
+
+    let mut vocabularies = Vocabularies::default();
+    if let Some(ref path) = option.icd10 {
+        vocabularies.load_icd10(path).map_err(io::Error::other)?;
+    }
+    for path in &option.mesh {
+        vocabularies.load_mesh(path).map_err(io::Error::other)?;
+    }
+    if let Some(ref path) = option.hpo {
+        vocabularies
+            .load_ontology("HPO", path)
+            .map_err(io::Error::other)?;
+    }
+    if let Some(ref path) = option.orphanet {
+        vocabularies
+            .load_ontology("Orphanet", path)
+            .map_err(io::Error::other)?;
+    }
+
+    // all names are read first, so that IDs lengthened on a collision do not depend on row order
+    let disease_ids = read_disease_ids(&option.input, &option.disease_normalization)?;
+    let mut lengthened_disease_ids =
+        Report::new("mgend_report_disease_id.tsv", &["disease", "name"]);
+    for (name, id) in disease_ids.lengthened() {
+        lengthened_disease_ids.add(&[id, name]);
+    }
+
+    let mut unknown_chromosomes = Report::new(
+        "mgend_report_chromosome.tsv",
+        &["chromosome", "assembly", "status"],
+    );
+    let mut consequence_issues = Report::new(
+        "mgend_report_consequence.tsv",
+        &["variant", "issue", "submitted", "predicted"],
+    );
+    let mut gene_symbols = Report::new(
+        "mgend_report_gene_symbol.tsv",
+        &["symbol", "status", "candidates"],
+    );
+    let mut xref_issues = Report::new(
+        "mgend_report_xref.tsv",
+        &["system", "value", "issue", "label"],
+    );
+    let mut count_issues = Report::new(
+        "mgend_report_denominator.tsv",
+        &["case", "issue", "denominators"],
+    );
+    // content of all rows sharing a case ID is read first, so that extended IDs do not depend on
+    // row order
+    let case_keys = read_case_keys(&option, &hgnc, &prefix_maps, &ror, &disease_ids)?;
+    let mut duplicate_cases = Report::new(
+        "mgend_report_duplicate_case.tsv",
+        &["case", "content", "id", "line"],
+    );
+    let mut case_index = HashMap::new();
+    let mut association_index = HashMap::new();
+    let mut identical_cases = 0;
+    let mut conflicting_cases = 0;
+    let mut gene_discordances = Report::new(
+        "mgend_report_gene_overlap.tsv",
+        &["variant", "submitted", "overlapping", "concordance"],
+    );
 
-        submissions
+    let headers = reader.headers()?.clone();
+    let source_file = option
+        .input
+        .file_name()
+        .map_or(String::new(), |x| x.to_string_lossy().to_string());
+
+    for result in reader.records() {
+        let raw = result?;
+        let row: Row = raw.deserialize(Some(&headers))?;
+        let mut record = Record::new(
+            &option.assembly,
+            &row,
+            &hgnc,
+            &prefix_maps,
+            &ror,
+            &option.disease_normalization,
+            &disease_ids,
+        );
+        if option.provenance {
+            record.provenance = Some(Provenance::new(
+                &source_file,
+                &raw,
+                option.min_cell_size.is_none(),
+            ));
+        }
+
+        // an unlisted contig is known if the reference has its sequence
+        match &record.chromosome {
+            Chromosome::Unknown(_) => {
+                unknown_chromosomes.add(&[&row.chr, &option.assembly.to_string(), "unknown"])
+            }
+            Chromosome::Unlisted(x) if !reference.as_ref().is_some_and(|r| r.contains(x)) => {
+                unknown_chromosomes.add(&[&row.chr, &option.assembly.to_string(), "unlisted"])
+            }
+            _ => {}
+        }
+
+        for x in XRef::from_record(&record) {
+            match x {
+                Err((system, value)) => xref_issues.add(&[system, &value, "malformed", ""]),
+                Ok(x) => {
+                    match vocabularies.validate(&x) {
+                        Some(Validation::Unknown) => {
+                            xref_issues.add(&[x.system(), x.id(), "unknown", ""])
+                        }
+                        Some(Validation::Obsolete(label)) => {
+                            xref_issues.add(&[x.system(), x.id(), "obsolete", label])
+                        }
+                        _ => {}
+                    }
+                    if let Some(node) = XRefNode::new(&x, &prefix_maps) {
+                        xref_nodes.insert(node.identifier().clone(), node);
+                    }
+                }
+            }
+        }
+
+        // GWAS results become associations without case counts
+        let key = content_key(&record);
+        let mut case = None;
+        let mut association = None;
+        let (id, duplicate) = if record.is_gwas() {
+            let mut x = Association::from(&record);
+            x.set_xref_labels(&vocabularies);
+            let duplicate = deduplicate(
+                &mut x,
+                &associations,
+                &association_index,
+                &case_keys,
+                &key,
+                |x| x.identifier(),
+                |x, key, significance| x.disambiguate(&record, key, significance),
+            );
+            let id = x.identifier().clone();
+            association = Some(x);
+            (id, duplicate)
+        } else {
+            let mut x = Case::from(&record);
+            x.set_xref_labels(&vocabularies);
+            x.set_infectious_agents(&record, &infectious_agents);
+            if let Some(k) = option.min_cell_size {
+                x.suppress(k);
+            }
+            let duplicate = deduplicate(
+                &mut x,
+                &cases,
+                &case_index,
+                &case_keys,
+                &key,
+                |x| x.identifier(),
+                |x, key, significance| x.disambiguate(&record, key, significance),
+            );
+            let id = x.identifier().clone();
+            case = Some(x);
+            (id, duplicate)
+        };
+
+        // an identical row still counts for its submission and submitters, which may differ
+        let identical = duplicate == Some("identical");
+        if let Some(duplicate) = duplicate {
+            let line = raw.position().map_or(0, |x| x.line()).to_string();
+            duplicate_cases.add(&[Case::id(&record).as_str(), duplicate, &id, &line]);
+            if identical {
+                identical_cases += 1;
+            } else {
+                conflicting_cases += 1;
+            }
+        }
+        if !identical {
+            if case.is_some() {
+                case_index.insert(id.clone(), cases.len());
+                for (issue, denominators) in Case::count_issues(&row) {
+                    count_issues.add(&[id.as_str(), issue, &denominators]);
+                }
+            } else {
+                association_index.insert(id.clone(), associations.len());
+            }
+        }
+
+        let submission = submissions
             .entry(Submission::id(&record))
-            .or_insert(Submission::from(&record))
-            .add_case(&case);
-
-        if let Some(symbols) = Gene::parse(&record) {
-            for symbol in symbols {
-                genes
-                    .entry(symbol.to_string())
-                    .or_insert(Gene::new(symbol, &option.hgnc));
+            .or_insert_with(|| Submission::from(&record));
+        submission.add_record(&record);
+        if !identical {
+            if let Some(ref case) = case {
+                submission.add_case(case);
+            }
+            if let Some(ref association) = association {
+                submission.add_association(association);
             }
         }
 
-        let variant = variants
-            .entry(Variant::id(&record))
-            .or_insert(Variant::from(&record));
+        for (name, inst) in submission::submitters(&row) {
+            let person = persons
+                .entry(Person::id(name, inst, &ror))
+                .or_insert_with(|| Person::new(name, inst, &ror));
+            person.add_submission(&record);
+            organizations
+                .entry(Organization::id(inst, &ror))
+                .or_insert_with(|| Organization::new(inst, &ror))
+                .add_case(inst, person.identifier(), &record, &id);
+        }
+        // the row adds only its line to the variant and the disease of the case it duplicates
+        if identical {
+            if let Some(variant) = variants.get_mut(&Variant::id(&record)) {
+                variant.add_source_line(record.provenance.as_ref());
+            }
+            if let Some(disease) = Disease::id(&record)
+                .and_then(|x| diseases.get_mut(&format!("{}:{}", MGEND_DISEASE.prefix, x)))
+            {
+                disease.add_source_line(record.provenance.as_ref());
+            }
+            continue;
+        }
 
-        variant.add_case(&case);
+        let variant = match variants.entry(Variant::id(&record)) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let mut variant = Variant::from(&record);
+                if let Some(ref bands) = cytobands {
+                    variant.set_cytoband(&record, bands);
+                }
+                if let Some(ref models) = gene_models {
+                    let symbols = variant.set_overlap(&record, models);
+                    match variant.gene_concordance() {
+                        None | Some(GeneConcordance::Concordant) => {}
+                        Some(x) => gene_discordances.add(&[
+                            Variant::id(&record),
+                            row.gene.clone().unwrap_or_default(),
+                            symbols.join(","),
+                            x.to_string(),
+                        ]),
+                    }
+                }
+                if let (Some(models), Some(reference)) = (&gene_models, &reference) {
+                    if let Some(issue) = variant.set_consequence(&record, models, reference)? {
+                        consequence_issues.add(&[
+                            Variant::id(&record),
+                            issue.to_string(),
+                            row.reference.clone().unwrap_or_default(),
+                            String::new(),
+                        ]);
+                    } else if variant.amino_acid_concordant() == Some(false) {
+                        consequence_issues.add(&[
+                            Variant::id(&record),
+                            "amino_acid_discordance".to_string(),
+                            row.amino_acid.clone().unwrap_or_default(),
+                            variant.protein_changes().join(","),
+                        ]);
+                    }
+                }
+                e.insert(variant)
+            }
+        };
+
+        if let Some(ref case) = case {
+            variant.add_case(case, &record);
+        }
+        if let Some(ref association) = association {
+            variant.add_association(association);
+        }
+
+        // genes submitted for the row or overlapping its variant, each linked once per row
+        let mut linked: HashSet<String> = HashSet::new();
+        for symbol in Gene::parse(&record)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(variant.overlapping_symbols())
+        {
+            if !linked.insert(Gene::id(symbol, &hgnc)) {
+                continue;
+            }
+            let gene = add_gene(&mut genes, &mut gene_symbols, symbol, &hgnc);
+            if record.is_gwas() {
+                gene.add_association(&record);
+            } else {
+                gene.add_case(&record);
+            }
+        }
 
         if let Ok(disease) = Disease::try_from(&record) {
             variant.add_disease(&disease);
 
-            diseases
-                .entry(format!(
-                    "{}:{}",
-                    MGEND_DISEASE.prefix,
-                    disease.id.to_string()
-                ))
-                .or_insert(disease)
-                .add_case(&case);
+            let entry = diseases
+                .entry(format!("{}:{}", MGEND_DISEASE.prefix, disease.id))
+                .or_insert_with(|| disease.clone());
+            entry.add_label(disease.label());
+            if let Some(ref case) = case {
+                entry.add_case(case);
+            }
+            if let Some(ref association) = association {
+                entry.add_association(association);
+            }
         }
 
-        cases.push(case);
+        cases.extend(case);
+        associations.extend(association);
 
         if option.rehearsal {
             break;
         }
     }
 
-    if option.directory.exists() && !option.directory.is_dir() {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("{} is not a directory", option.directory.to_string_lossy()),
-        ))?
-    } else if !option.directory.exists() {
-        std::fs::create_dir_all(&option.directory)?
+    let mut unmapped_diseases = Report::new(
+        "mgend_report_disease_mapping.tsv",
+        &["disease", "label", "alt_label"],
+    );
+    if !vocabularies.is_empty() {
+        for disease in diseases.values_mut() {
+            disease.set_xref_labels(&vocabularies);
+        }
+    }
+    if let Some(ref ontologies) = ontologies {
+        for disease in diseases.values_mut() {
+            if !disease.set_mapping(ontologies, &option.disease_normalization) {
+                unmapped_diseases.add(&[
+                    disease.identifier().as_str(),
+                    disease.label(),
+                    &disease.alt_label().join("|"),
+                ]);
+            }
+        }
     }
 
+    create_directory(&option.directory)?;
+
+    let bands = cytobands.as_ref().map(|x| {
+        x.iter()
+            .map(|(chr, band)| CytoBand::new(&option.assembly, chr, band))
+            .collect::<Vec<CytoBand>>()
+    });
+
+    let mut dataset = Dataset::new(
+        dataset_config,
+        source,
+        &option.assembly,
+        option.min_cell_size,
+    );
     match option.format {
         Format::JSONLD => {
+            write_jsonld!(
+                option.directory,
+                "mgend_association.jsonld.gz",
+                Association::contexts(),
+                associations
+            )?;
+            dataset.add_subset(
+                "association",
+                "mgend_association.jsonld.gz",
+                associations.len(),
+                None,
+            );
             write_jsonld!(
                 option.directory,
                 "mgend_case.jsonld.gz",
                 Case::contexts(),
                 cases
             )?;
+            dataset.add_subset("case", "mgend_case.jsonld.gz", cases.len(), None);
             write_jsonld!(
                 option.directory,
                 "mgend_variant.jsonld.gz",
                 Variant::contexts(),
                 variants.values().collect::<Vec<&Variant>>()
             )?;
+            dataset.add_subset("variant", "mgend_variant.jsonld.gz", variants.len(), None);
             write_jsonld!(
                 option.directory,
                 "mgend_submission.jsonld.gz",
                 Submission::contexts(),
                 submissions.values().collect::<Vec<&Submission>>()
             )?;
+            dataset.add_subset(
+                "submission",
+                "mgend_submission.jsonld.gz",
+                submissions.len(),
+                None,
+            );
             write_jsonld!(
                 option.directory,
                 "mgend_disease.jsonld.gz",
                 Disease::contexts(),
                 diseases.values().collect::<Vec<&Disease>>()
             )?;
+            dataset.add_subset("disease", "mgend_disease.jsonld.gz", diseases.len(), None);
             write_jsonld!(
                 option.directory,
                 "mgend_gene.jsonld.gz",
                 Gene::contexts(),
                 genes.values().collect::<Vec<&Gene>>()
-            )?
+            )?;
+            dataset.add_subset("gene", "mgend_gene.jsonld.gz", genes.len(), None);
+            write_jsonld!(
+                option.directory,
+                "mgend_organization.jsonld.gz",
+                Organization::contexts(),
+                organizations.values().collect::<Vec<&Organization>>()
+            )?;
+            dataset.add_subset(
+                "organization",
+                "mgend_organization.jsonld.gz",
+                organizations.len(),
+                None,
+            );
+            write_jsonld!(
+                option.directory,
+                "mgend_person.jsonld.gz",
+                Person::contexts(),
+                persons.values().collect::<Vec<&Person>>()
+            )?;
+            dataset.add_subset("person", "mgend_person.jsonld.gz", persons.len(), None);
+            if let Some(ref bands) = bands {
+                write_jsonld!(
+                    option.directory,
+                    "mgend_cytoband.jsonld.gz",
+                    CytoBand::contexts(),
+                    bands
+                )?;
+                dataset.add_subset("cytoband", "mgend_cytoband.jsonld.gz", bands.len(), None);
+            }
+            if !xref_nodes.is_empty() {
+                write_jsonld!(
+                    option.directory,
+                    "mgend_xref.jsonld.gz",
+                    XRefNode::contexts(),
+                    xref_nodes.values().collect::<Vec<&XRefNode>>()
+                )?;
+                dataset.add_subset("xref", "mgend_xref.jsonld.gz", xref_nodes.len(), None);
+            }
         }
         Format::Turtle => {
-            write_turtle(
+            let triples = write_turtle(
+                &option.directory,
+                "mgend_association.ttl.gz",
+                &Association::namespaces(),
+                &associations,
+            )?;
+            dataset.add_subset(
+                "association",
+                "mgend_association.ttl.gz",
+                associations.len(),
+                Some(triples),
+            );
+            let triples = write_turtle(
                 &option.directory,
                 "mgend_case.ttl.gz",
                 &Case::namespaces(),
                 &cases,
             )?;
-            write_turtle(
+            dataset.add_subset("case", "mgend_case.ttl.gz", cases.len(), Some(triples));
+            let triples = write_turtle(
                 &option.directory,
                 "mgend_variant.ttl.gz",
                 &Variant::namespaces(),
                 variants.values(),
             )?;
-            write_turtle(
+            dataset.add_subset(
+                "variant",
+                "mgend_variant.ttl.gz",
+                variants.len(),
+                Some(triples),
+            );
+            let triples = write_turtle(
                 &option.directory,
                 "mgend_submission.ttl.gz",
                 &Submission::namespaces(),
                 submissions.values(),
             )?;
-            write_turtle(
+            dataset.add_subset(
+                "submission",
+                "mgend_submission.ttl.gz",
+                submissions.len(),
+                Some(triples),
+            );
+            let triples = write_turtle(
                 &option.directory,
                 "mgend_disease.ttl.gz",
                 &Disease::namespaces(),
                 diseases.values(),
             )?;
-            write_turtle(
+            dataset.add_subset(
+                "disease",
+                "mgend_disease.ttl.gz",
+                diseases.len(),
+                Some(triples),
+            );
+            let triples = write_turtle(
                 &option.directory,
                 "mgend_gene.ttl.gz",
                 &Gene::namespaces(),
                 genes.values(),
-            )?
+            )?;
+            dataset.add_subset("gene", "mgend_gene.ttl.gz", genes.len(), Some(triples));
+            let triples = write_turtle(
+                &option.directory,
+                "mgend_organization.ttl.gz",
+                &Organization::namespaces(),
+                organizations.values(),
+            )?;
+            dataset.add_subset(
+                "organization",
+                "mgend_organization.ttl.gz",
+                organizations.len(),
+                Some(triples),
+            );
+            let triples = write_turtle(
+                &option.directory,
+                "mgend_person.ttl.gz",
+                &Person::namespaces(),
+                persons.values(),
+            )?;
+            dataset.add_subset(
+                "person",
+                "mgend_person.ttl.gz",
+                persons.len(),
+                Some(triples),
+            );
+            if let Some(ref bands) = bands {
+                let triples = write_turtle(
+                    &option.directory,
+                    "mgend_cytoband.ttl.gz",
+                    &CytoBand::namespaces(),
+                    bands,
+                )?;
+                dataset.add_subset(
+                    "cytoband",
+                    "mgend_cytoband.ttl.gz",
+                    bands.len(),
+                    Some(triples),
+                );
+            }
+            if !xref_nodes.is_empty() {
+                let triples = write_turtle(
+                    &option.directory,
+                    "mgend_xref.ttl.gz",
+                    &XRefNode::namespaces(),
+                    xref_nodes.values(),
+                )?;
+                dataset.add_subset("xref", "mgend_xref.ttl.gz", xref_nodes.len(), Some(triples));
+            }
         }
     };
 
+    write_turtle_plain(
+        &option.directory,
+        "mgend_dataset.ttl",
+        &Dataset::namespaces(),
+        &dataset,
+    )?;
+
+    if !lengthened_disease_ids.is_empty() {
+        eprintln!(
+            "warning: {} disease IDs lengthened as their names share an ID",
+            lengthened_disease_ids.len()
+        );
+    }
+    if !unknown_chromosomes.is_empty() {
+        eprintln!(
+            "warning: {} chromosome names unknown or unlisted for {}",
+            unknown_chromosomes.len(),
+            option.assembly
+        );
+    }
+    let conflicting = variants.values().filter(|x| x.is_conflicting()).count();
+    if conflicting > 0 {
+        eprintln!(
+            "warning: {} variants with conflicting interpretations",
+            conflicting
+        );
+    }
+    if !duplicate_cases.is_empty() {
+        eprintln!(
+            "warning: {} rows sharing a case ID, {} identical and {} conflicting",
+            identical_cases + conflicting_cases,
+            identical_cases,
+            conflicting_cases
+        );
+    }
+    if !gene_symbols.is_empty() {
+        eprintln!(
+            "warning: {} gene symbols not resolved to an HGNC ID",
+            gene_symbols.len()
+        );
+    }
+    if !invalid_ror_ids.is_empty() {
+        eprintln!(
+            "warning: {} lines of the ROR mapping without a valid ROR ID",
+            invalid_ror_ids.len()
+        );
+    }
+    let mut xref_conflicts = Report::new(
+        "mgend_report_disease_xref.tsv",
+        &["disease", "label", "system", "xrefs"],
+    );
+    for disease in diseases.values() {
+        for (system, xrefs) in disease.conflicts() {
+            xref_conflicts.add(&[
+                disease.identifier().as_str(),
+                disease.label(),
+                system,
+                &xrefs
+                    .iter()
+                    .map(|x| x.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(","),
+            ]);
+        }
+    }
+
+    if !count_issues.is_empty() {
+        eprintln!(
+            "warning: {} cases with inconsistent numerators or denominators",
+            count_issues.len()
+        );
+    }
+    let mut submission_conflicts = Report::new(
+        "mgend_report_submission.tsv",
+        &["submission", "field", "values"],
+    );
+    for submission in submissions.values() {
+        if submission.is_not_provided() {
+            continue;
+        }
+        for (field, values) in submission.conflicts() {
+            submission_conflicts.add(&[submission.identifier().as_str(), field, &values]);
+        }
+    }
+    if !submission_conflicts.is_empty() {
+        eprintln!(
+            "warning: {} fields differ between the rows of a submission",
+            submission_conflicts.len()
+        );
+    }
+
+    if !xref_issues.is_empty() {
+        eprintln!(
+            "warning: {} condition or code identifiers malformed, unknown or obsolete",
+            xref_issues.len()
+        );
+    }
+    lengthened_disease_ids.write(&option.directory)?;
+    unknown_chromosomes.write(&option.directory)?;
+    xref_issues.write(&option.directory)?;
+    gene_symbols.write(&option.directory)?;
+    gene_discordances.write(&option.directory)?;
+    consequence_issues.write(&option.directory)?;
+    count_issues.write(&option.directory)?;
+    duplicate_cases.write(&option.directory)?;
+    invalid_ror_ids.write(&option.directory)?;
+    xref_conflicts.write(&option.directory)?;
+    submission_conflicts.write(&option.directory)?;
+    unmapped_diseases.write(&option.directory)?;
+
     Ok(())
 }
 
+/// Report the entities added, removed or changed between two releases
+fn diff(option: DiffOptions) -> io::Result<()> {
+    create_directory(&option.directory)?;
+
+    let mut releases = Vec::new();
+    for (name, path) in [("old", &option.old), ("new", &option.new)] {
+        let directory = if path.is_dir() {
+            path.clone()
+        } else {
+            let (assembly, hgnc) = match (&option.assembly, &option.hgnc) {
+                (Some(assembly), Some(hgnc)) => (assembly, hgnc),
+                _ => Err(io::Error::other(
+                    "--assembly and --hgnc are required to convert input files",
+                ))?,
+            };
+
+            let mut directory = option.directory.clone();
+            directory.push(name);
+            convert(Options::new(
+                assembly.clone(),
+                Format::JSONLD,
+                hgnc.clone(),
+                directory.clone(),
+                path.clone(),
+            ))?;
+            directory
+        };
+
+        releases.push(Release::from_directory(&directory).map_err(io::Error::other)?);
+    }
+
+    let diff = Diff::new(&releases[0], &releases[1]);
+    diff.write(&option.directory)?;
+    print!("{}", diff);
+
+    Ok(())
+}
+
+fn create_directory(path: &Path) -> io::Result<()> {
+    if path.exists() && !path.is_dir() {
+        Err(io::Error::other(format!(
+            "{} is not a directory",
+            path.to_string_lossy()
+        )))?
+    } else if !path.exists() {
+        std::fs::create_dir_all(path)?
+    }
+
+    Ok(())
+}
+
+/// Disambiguate the ID of an item sharing it with items of other content, as for rows of a
+/// submission with the same variant and disease. `Some("identical")` if the same item is already
+/// indexed, `Some("conflicting")` if the ID was disambiguated by the content `key` of its row.
+fn deduplicate<T: Serialize>(
+    item: &mut T,
+    items: &[T],
+    index: &HashMap<String, usize>,
+    case_keys: &CaseKeys,
+    key: &str,
+    id: impl Fn(&T) -> &String,
+    disambiguate: impl Fn(&mut T, &str, bool),
+) -> Option<&'static str> {
+    let conflicting = match case_keys.disambiguation(id(item), key) {
+        Some(significance) => {
+            disambiguate(item, key, significance);
+            true
+        }
+        None => false,
+    };
+
+    match index.get(id(item)) {
+        Some(&i) if content(&items[i]) == content(item) => Some("identical"),
+        _ => conflicting.then_some("conflicting"),
+    }
+}
+
+/// Read the content keys of the cases and associations of the input file by their ID
+fn read_case_keys(
+    option: &Options,
+    hgnc: &HGNC,
+    prefix_maps: &PrefixMaps,
+    ror: &RorMapping,
+    disease_ids: &DiseaseIds,
+) -> io::Result<CaseKeys> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(b'\t')
+        .from_reader(reader::open(&option.input)?);
+
+    let mut keys = CaseKeys::default();
+    for result in reader.deserialize::<Row>() {
+        let row = result?;
+        let record = Record::new(
+            &option.assembly,
+            &row,
+            hgnc,
+            prefix_maps,
+            ror,
+            &option.disease_normalization,
+            disease_ids,
+        );
+
+        keys.add(
+            &Case::id(&record),
+            content_key(&record),
+            row.clinical_significance.to_string(),
+        );
+    }
+
+    Ok(keys)
+}
+
+/// Read the disease names of the input file to mint their IDs
+fn read_disease_ids<P: AsRef<Path>>(
+    path: P,
+    normalization: &[Normalization],
+) -> io::Result<DiseaseIds> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(b'\t')
+        .from_reader(reader::open(path)?);
+
+    let mut names = Vec::new();
+    for result in reader.deserialize::<Row>() {
+        names.extend(Record::disease_name(&result?, normalization));
+    }
+
+    Ok(DiseaseIds::new(names))
+}
+
+/// Add the gene of a submitted or overlapping symbol, reporting symbols without a unique HGNC ID
+fn add_gene<'g>(
+    genes: &'g mut HashMap<String, Gene>,
+    report: &mut Report,
+    symbol: &str,
+    hgnc: &HGNC,
+) -> &'g mut Gene {
+    match hgnc.resolve(symbol) {
+        Resolution::Ambiguous(ids) => report.add(&[
+            symbol,
+            "ambiguous",
+            &ids.iter()
+                .map(|x| format!("HGNC:{}", x))
+                .collect::<Vec<String>>()
+                .join(","),
+        ]),
+        x @ (Resolution::Withdrawn | Resolution::NotFound) => report.add(&[symbol, x.status(), ""]),
+        _ => {}
+    }
+
+    let gene = genes
+        .entry(Gene::id(symbol, hgnc))
+        .or_insert_with(|| Gene::new(symbol, hgnc));
+    gene.add_symbol(symbol);
+    gene
+}
+
 fn write_jsonld<P: AsRef<Path>, S: Serialize, T: Serialize>(
     path: P,
     filename: &str,
@@ -237,12 +1232,13 @@ fn write_jsonld<P: AsRef<Path>, S: Serialize, T: Serialize>(
     Ok(())
 }
 
+/// Write the collection to gzipped Turtle and return the number of triples
 fn write_turtle<'a, P: AsRef<Path>, T: ToTurtle + 'a, I: IntoIterator<Item = &'a T>>(
     path: P,
     filename: &str,
     namespaces: &Vec<NameSpace>,
     collection: I,
-) -> io::Result<()> {
+) -> io::Result<usize> {
     let mut out = PathBuf::from(path.as_ref());
     out.push(filename);
 
@@ -255,10 +1251,284 @@ fn write_turtle<'a, P: AsRef<Path>, T: ToTurtle + 'a, I: IntoIterator<Item = &'a
         writer.write_all(format!("@prefix {}: <{}> .\n", ns.prefix, ns.uri).as_bytes())?;
     }
 
+    let mut triples = 0;
     for item in collection {
+        let ttl = item.to_ttl()?;
+        triples += turtle::count_triples(&ttl);
         writer.write_all(b"\n")?;
-        writer.write_all(item.to_ttl()?.as_bytes())?;
+        writer.write_all(ttl.as_bytes())?;
     }
 
+    Ok(triples)
+}
+
+fn write_turtle_plain<P: AsRef<Path>, T: ToTurtle>(
+    path: P,
+    filename: &str,
+    namespaces: &Vec<NameSpace>,
+    item: &T,
+) -> io::Result<()> {
+    let mut out = PathBuf::from(path.as_ref());
+    out.push(filename);
+
+    let mut writer = BufWriter::new(File::create(&out)?);
+
+    eprintln!("writing {:?}", &out);
+
+    for ns in namespaces {
+        writer.write_all(format!("@prefix {}: <{}> .\n", ns.prefix, ns.uri).as_bytes())?;
+    }
+    writer.write_all(b"\n")?;
+    writer.write_all(item.to_ttl()?.as_bytes())?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Input file of rows given as columns and values, with zero for counts not given
+    fn input(rows: &[&[(&str, &str)]]) -> String {
+        let mut columns: Vec<String> = [
+            "Chr",
+            "RsID",
+            "Start",
+            "End",
+            "Ref",
+            "Alt",
+            "Gene",
+            "AminoAcid",
+            "HGVS",
+            "CS",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let sex = ["Male", "Female", "MixedGender", "Unknown", "NotProvided"];
+        let age = [
+            "0_9",
+            "10_19",
+            "20_29",
+            "30_39",
+            "40_49",
+            "50_59",
+            "60_69",
+            "70_79",
+            "80_89",
+            "90_99",
+            "100",
+            "Unknown",
+            "NotProvided",
+            "Other",
+        ];
+        let counts: Vec<String> = sex
+            .iter()
+            .map(|x| format!("Sex{}", x))
+            .chain(age.iter().map(|x| format!("Age{}", x)))
+            .chain(age.iter().map(|x| format!("AgeOfOnset{}", x)))
+            .flat_map(|x| [format!("{}Numerator", x), format!("{}Denominator", x)])
+            .collect();
+        columns.extend(counts.iter().cloned());
+        columns.extend(
+            [
+                "CodeType",
+                "CodeValue",
+                "ConditionIDType",
+                "ConditionIDValue",
+                "PreferredConditionName",
+                "DiseaseName",
+                "DiseaseArea1",
+                "DiseaseArea2",
+                "DataOrigin",
+                "AlleleOrigin",
+                "SubmissionID",
+                "SubmitterName1",
+                "SubmitterInstitute1",
+                "SubmitterName2",
+                "SubmitterInstitute2",
+                "Citation",
+            ]
+            .iter()
+            .map(|x| x.to_string()),
+        );
+
+        let mut lines = vec![columns.join("\t")];
+        for row in rows {
+            lines.push(
+                columns
+                    .iter()
+                    .map(|c| match row.iter().find(|(k, _)| k == c) {
+                        Some((_, v)) => v.to_string(),
+                        None if counts.contains(c) => "0".to_string(),
+                        None => String::new(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\t"),
+            );
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Empty temporary directory of a test
+    fn temp_dir(name: &str) -> io::Result<PathBuf> {
+        let dir = std::env::temp_dir().join(format!("mgend-rdf-{}-{}", name, std::process::id()));
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        create_directory(&dir)?;
+        Ok(dir)
+    }
+
+    /// hgnc_complete_set.txt of BRCA1 only
+    fn write_hgnc(dir: &Path) -> io::Result<PathBuf> {
+        let hgnc = dir.join("hgnc.tsv");
+        std::fs::write(
+            &hgnc,
+            "hgnc_id\tsymbol\tname\tlocus_group\tlocus_type\tstatus\tlocation\tlocation_sortable\t\
+             alias_symbol\talias_name\tprev_symbol\tprev_name\tgene_group\tgene_group_id\t\
+             date_approved_reserved\tdate_symbol_changed\tdate_name_changed\tdate_modified\t\
+             entrez_id\tensembl_gene_id\n\
+             HGNC:1100\tBRCA1\tBRCA1 DNA repair associated\tprotein-coding gene\t\tApproved\t\
+             17q21.31\t\t\t\t\t\t\t\t\t\t\t\t672\tENSG00000012048\n",
+        )?;
+        Ok(hgnc)
+    }
+
+    /// A row of a BRCA1 variant of the same disease and submission, counting a case of `sex`
+    fn row(significance: &'static str, sex: &'static str) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("Chr", "chr17"),
+            ("Start", "43045712"),
+            ("End", "43045712"),
+            ("Ref", "A"),
+            ("Alt", "G"),
+            ("Gene", "BRCA1"),
+            ("CS", significance),
+            (sex, "1"),
+            ("SexMaleDenominator", "5"),
+            ("SexFemaleDenominator", "5"),
+            ("CodeType", "ICD10"),
+            ("CodeValue", "C50.9"),
+            ("DiseaseName", "Breast-ovarian cancer"),
+            ("AlleleOrigin", "Germline"),
+            ("SubmissionID", "MGS000001"),
+            ("SubmitterName1", "Taro Yamada"),
+        ]
+    }
+
+    /// IDs of the `@graph` of a JSON-LD output file
+    fn read_ids(path: &Path) -> io::Result<Vec<String>> {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut reader::open(path)?, &mut buf)?;
+        let json: Value = serde_json::from_str(&buf)?;
+
+        let mut ids: Vec<String> = json["@graph"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|x| x["id"].as_str().map(|x| x.to_string()))
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    #[test]
+    fn case_ids_independent_of_output_options() -> io::Result<()> {
+        let dir = temp_dir("case-ids")?;
+        let hgnc = write_hgnc(&dir)?;
+
+        // two rows of the same variant, disease and submission, whose IDs are extended by content
+        let path = dir.join("input.tsv");
+        std::fs::write(
+            &path,
+            input(&[
+                &row("Pathogenic", "SexMaleNumerator"),
+                &row("Pathogenic", "SexFemaleNumerator"),
+            ]),
+        )?;
+        let prefix_map = dir.join("prefix_map.tsv");
+        std::fs::write(&prefix_map, "icd10\thttp://example.org/icd10/$1\n")?;
+
+        let mut ids = Vec::new();
+        for (name, min_cell_size, prefix_map) in [
+            ("default", None, None),
+            ("suppressed", Some(3), None),
+            ("prefix_map", None, Some(prefix_map)),
+        ] {
+            let output = dir.join(name);
+            let mut option = Options::new(
+                Assembly::GRCh38,
+                Format::JSONLD,
+                hgnc.clone(),
+                output.clone(),
+                path.clone(),
+            );
+            option.min_cell_size = min_cell_size;
+            option.prefix_map = prefix_map;
+
+            convert(option)?;
+            ids.push(read_ids(&output.join("mgend_case.jsonld.gz"))?);
+        }
+        std::fs::remove_dir_all(&dir)?;
+
+        assert_eq!(ids[0].len(), 2, "{:?}", ids[0]);
+        assert!(ids[0].iter().all(|x| x.contains("_Germline_")));
+        assert_eq!(ids[0], ids[1]);
+        assert_eq!(ids[0], ids[2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diff_reclassified_case_as_changed() -> io::Result<()> {
+        let dir = temp_dir("diff")?;
+        let hgnc = write_hgnc(&dir)?;
+
+        // two rows of the same variant, disease and submission, whose IDs are extended by content
+        let old = dir.join("old.tsv");
+        std::fs::write(
+            &old,
+            input(&[
+                &row("Pathogenic", "SexMaleNumerator"),
+                &row("Pathogenic", "SexFemaleNumerator"),
+            ]),
+        )?;
+        let new = dir.join("new.tsv");
+        std::fs::write(
+            &new,
+            input(&[
+                &row("Likely pathogenic", "SexMaleNumerator"),
+                &row("Pathogenic", "SexFemaleNumerator"),
+            ]),
+        )?;
+
+        diff(DiffOptions {
+            assembly: Some(Assembly::GRCh38),
+            hgnc: Some(hgnc),
+            directory: dir.join("diff"),
+            old,
+            new,
+        })?;
+
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(
+            dir.join("diff").join("mgend_diff.json"),
+        )?)?;
+        let cases: Vec<&Value> = json["changes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|x| x["kind"] == "case")
+            .collect();
+        std::fs::remove_dir_all(&dir)?;
+
+        assert_eq!(cases.len(), 1, "{:?}", cases);
+        assert_eq!(cases[0]["change"], "changed");
+        assert!(cases[0]["id"].as_str().unwrap().contains("_Germline_"));
+        assert_eq!(cases[0]["fields"][0]["field"], "case_significance");
+        assert_eq!(cases[0]["fields"].as_array().unwrap().len(), 1);
+
+        Ok(())
+    }
+}
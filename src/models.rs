@@ -1,8 +1,17 @@
+pub mod annotation;
+pub mod chromosome;
+pub mod consequence;
 pub mod context;
+pub mod cytoband;
 pub mod hgnc;
+pub mod infectious_agent;
 pub mod input;
 pub mod name_space;
+pub mod ontology;
 pub mod output;
+pub mod prefix_map;
+pub mod reference;
+pub mod vocabulary;
 
 macro_rules! regex {
     ($re:literal $(,)?) => {{
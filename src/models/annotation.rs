@@ -0,0 +1,308 @@
+use crate::models::chromosome::Chromosome;
+use crate::models::regex;
+use crate::{reader, Assembly, VCFInt};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Width of the bins used to look up features by position
+const BIN_SIZE: VCFInt = 100_000;
+
+#[derive(Debug)]
+pub struct GeneModel {
+    pub id: String,
+    pub symbol: Option<String>,
+    pub chromosome: String,
+    /// 1-based
+    pub start: VCFInt,
+    /// 1-based, inclusive
+    pub end: VCFInt,
+    pub transcripts: Vec<Transcript>,
+}
+
+#[derive(Debug)]
+pub struct Transcript {
+    pub id: String,
+    pub start: VCFInt,
+    pub end: VCFInt,
+    pub strand: Strand,
+    /// Sorted by position
+    pub exons: Vec<(VCFInt, VCFInt)>,
+    /// Sorted by position, including the stop codon
+    pub cds: Vec<(VCFInt, VCFInt)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// Genes and transcripts from a GENCODE/Ensembl/RefSeq GTF or GFF3
+pub struct GeneModels {
+    genes: Vec<GeneModel>,
+    index: HashMap<(String, VCFInt), Vec<usize>>,
+}
+
+struct Feature {
+    chromosome: String,
+    typ: String,
+    start: VCFInt,
+    end: VCFInt,
+    strand: Strand,
+    /// Values by tag; GFF3 tags may have several, separated by commas
+    attributes: HashMap<String, Vec<String>>,
+}
+
+impl Feature {
+    fn parse(line: &str, gtf: bool, assembly: &Assembly) -> Result<Option<Self>, String> {
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols.len() != 9 {
+            return Err(format!("expected 9 columns, found {}", cols.len()));
+        }
+
+        let chromosome = match Chromosome::new(cols[0], assembly).resolved() {
+            Some(x) => x.to_string(),
+            None => return Ok(None),
+        };
+
+        let attributes = if gtf {
+            regex!(r#"(\S+) "([^"]*)""#)
+                .captures_iter(cols[8])
+                .map(|caps| (caps[1].to_string(), vec![caps[2].to_string()]))
+                .collect()
+        } else {
+            cols[8]
+                .split(';')
+                .filter_map(|x| x.split_once('='))
+                .map(|(k, v)| {
+                    (
+                        percent_decode(k.trim()),
+                        v.trim().split(',').map(percent_decode).collect(),
+                    )
+                })
+                .collect()
+        };
+
+        Ok(Some(Feature {
+            chromosome,
+            typ: cols[2].to_string(),
+            start: cols[3].parse().map_err(|e| format!("{}: {}", cols[3], e))?,
+            end: cols[4].parse().map_err(|e| format!("{}: {}", cols[4], e))?,
+            strand: match cols[6] {
+                "-" => Strand::Reverse,
+                _ => Strand::Forward,
+            },
+            attributes,
+        }))
+    }
+
+    fn attribute(&self, keys: &[&str]) -> Option<String> {
+        self.attributes(keys).into_iter().next()
+    }
+
+    /// All values of the first of `keys` present, e.g. the `Parent`s of an exon
+    fn attributes(&self, keys: &[&str]) -> Vec<String> {
+        keys.iter()
+            .find_map(|k| self.attributes.get(*k))
+            .map_or(Vec::new(), |x| {
+                x.iter().map(|x| strip_id_prefix(x).to_string()).collect()
+            })
+    }
+}
+
+/// Decode the `%XX` escapes of a GFF3 column, e.g. `%3B` for `;` and `%2C` for `,`
+fn percent_decode(x: &str) -> String {
+    let bytes = x.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| x.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(b) => {
+                decoded.push(b);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Strip the `gene:`, `transcript:` (Ensembl) and `gene-`, `rna-` (RefSeq) prefixes of GFF3 IDs
+fn strip_id_prefix(id: &str) -> &str {
+    ["gene:", "transcript:", "gene-", "rna-"]
+        .iter()
+        .find_map(|x| id.strip_prefix(x))
+        .unwrap_or(id)
+}
+
+fn is_gene(typ: &str) -> bool {
+    matches!(typ, "gene" | "ncRNA_gene" | "pseudogene")
+}
+
+fn is_transcript(typ: &str) -> bool {
+    matches!(
+        typ,
+        "transcript" | "mRNA" | "lnc_RNA" | "ncRNA" | "miRNA" | "snRNA" | "snoRNA" | "rRNA"
+    ) || typ.ends_with("_transcript")
+}
+
+/// Sort and merge overlapping or adjacent ranges, e.g. a CDS and its separate stop codon
+fn merge(mut ranges: Vec<(VCFInt, VCFInt)>) -> Vec<(VCFInt, VCFInt)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(VCFInt, VCFInt)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+impl GeneModels {
+    pub fn from_path<P: AsRef<Path>>(path: P, assembly: &Assembly) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let gtf = reader::extension(path.as_ref()).is_some_and(|x| x == "gtf");
+
+        let mut genes: Vec<GeneModel> = Vec::new();
+        let mut gene_index: HashMap<String, usize> = HashMap::new();
+        let mut transcripts: Vec<(String, Transcript)> = Vec::new();
+        let mut transcript_index: HashMap<String, usize> = HashMap::new();
+        let mut exons: Vec<(String, (VCFInt, VCFInt))> = Vec::new();
+        let mut cds: Vec<(String, (VCFInt, VCFInt))> = Vec::new();
+
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| err(&e))?;
+            // GFF3 sequences after ##FASTA or a FASTA header run to the end of the file
+            if !gtf && (line.starts_with("##FASTA") || line.starts_with('>')) {
+                break;
+            }
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+
+            let feature = match Feature::parse(&line, gtf, assembly)
+                .map_err(|e| err(&format!("line {}: {}", i + 1, e)))?
+            {
+                Some(x) => x,
+                None => continue,
+            };
+
+            if is_gene(&feature.typ) {
+                let id = match feature.attribute(&["gene_id", "ID"]) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                gene_index.insert(id.clone(), genes.len());
+                genes.push(GeneModel {
+                    id,
+                    symbol: feature.attribute(&["gene_name", "Name", "gene"]),
+                    chromosome: feature.chromosome,
+                    start: feature.start,
+                    end: feature.end,
+                    transcripts: Vec::new(),
+                });
+            } else if is_transcript(&feature.typ) {
+                let gene_id = feature.attribute(&["gene_id", "Parent"]);
+                let key = feature.attribute(&["ID", "transcript_id"]);
+                let id = feature.attribute(&["transcript_id", "ID"]);
+                if let (Some(gene_id), Some(key), Some(id)) = (gene_id, key, id) {
+                    transcript_index.insert(key, transcripts.len());
+                    transcripts.push((
+                        gene_id,
+                        Transcript {
+                            id,
+                            start: feature.start,
+                            end: feature.end,
+                            strand: feature.strand,
+                            exons: Vec::new(),
+                            cds: Vec::new(),
+                        },
+                    ));
+                }
+            } else if matches!(feature.typ.as_str(), "exon" | "CDS" | "stop_codon") {
+                for parent in feature.attributes(&["Parent", "transcript_id"]) {
+                    let v = match feature.typ.as_str() {
+                        "exon" => &mut exons,
+                        _ => &mut cds,
+                    };
+                    v.push((parent, (feature.start, feature.end)));
+                }
+            }
+        }
+
+        for (parent, exon) in exons {
+            if let Some(&i) = transcript_index.get(&parent) {
+                transcripts[i].1.exons.push(exon);
+            }
+        }
+        for (parent, x) in cds {
+            if let Some(&i) = transcript_index.get(&parent) {
+                transcripts[i].1.cds.push(x);
+            }
+        }
+
+        for (gene_id, mut transcript) in transcripts {
+            transcript.exons.sort_unstable();
+            transcript.cds = merge(transcript.cds);
+
+            if let Some(&i) = gene_index.get(&gene_id) {
+                genes[i].transcripts.push(transcript);
+            }
+        }
+
+        let mut index: HashMap<(String, VCFInt), Vec<usize>> = HashMap::new();
+        for (i, gene) in genes.iter().enumerate() {
+            for bin in gene.start / BIN_SIZE..=gene.end / BIN_SIZE {
+                index
+                    .entry((gene.chromosome.clone(), bin))
+                    .or_default()
+                    .push(i);
+            }
+        }
+
+        Ok(GeneModels { genes, index })
+    }
+
+    /// Genes overlapping the 1-based closed interval `[start, end]`
+    pub fn find(&self, chromosome: &Chromosome, start: VCFInt, end: VCFInt) -> Vec<&GeneModel> {
+        let (start, end) = (start.min(end), start.max(end));
+
+        let mut found: Vec<usize> = (start / BIN_SIZE..=end / BIN_SIZE)
+            .filter_map(|bin| self.index.get(&(chromosome.name().to_string(), bin)))
+            .flatten()
+            .copied()
+            .filter(|&i| self.genes[i].start <= end && start <= self.genes[i].end)
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+
+        found.into_iter().map(|i| &self.genes[i]).collect()
+    }
+}
+
+impl GeneModel {
+    /// Transcripts overlapping the 1-based closed interval `[start, end]`
+    pub fn transcripts(&self, start: VCFInt, end: VCFInt) -> impl Iterator<Item = &Transcript> {
+        let (start, end) = (start.min(end), start.max(end));
+
+        self.transcripts
+            .iter()
+            .filter(move |x| x.start <= end && start <= x.end)
+    }
+}
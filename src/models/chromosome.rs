@@ -0,0 +1,159 @@
+use crate::models::regex;
+use crate::Assembly;
+use std::fmt::{Display, Formatter};
+
+const PRIMARY: [&str; 25] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16", "17",
+    "18", "19", "20", "21", "22", "X", "Y", "MT",
+];
+
+/// RefSeq accession versions of NC_000001 - NC_000024 for GRCh37; GRCh38 is one ahead
+const REFSEQ_VERSIONS_GRCH37: [u32; 24] = [
+    10, 11, 11, 11, 9, 11, 13, 10, 11, 10, 9, 11, 10, 8, 9, 9, 10, 9, 9, 10, 8, 10, 10, 9,
+];
+
+/// UCSC hg19 names of the GRCh37 alternate haplotypes
+const UCSC_HAPLOTYPES_GRCH37: [(&str, &str); 9] = [
+    ("chr6_apd_hap1", "GL000250.1"),
+    ("chr6_cox_hap2", "GL000251.1"),
+    ("chr6_dbb_hap3", "GL000252.1"),
+    ("chr6_mann_hap4", "GL000253.1"),
+    ("chr6_mcf_hap5", "GL000254.1"),
+    ("chr6_qbl_hap6", "GL000255.1"),
+    ("chr6_ssto_hap7", "GL000256.1"),
+    ("chr4_ctg9_hap1", "GL000257.1"),
+    ("chr17_ctg5_hap1", "GL000258.1"),
+];
+
+/// Chromosome name in the canonical form used for variant IRIs and FALDO references
+///
+/// Primary assembly units are named as in Ensembl (`1`-`22`, `X`, `Y`, `MT`),
+/// alternate loci, unlocalized and unplaced scaffolds by their versioned GenBank accession
+/// (e.g. `KI270706.1`).
+#[derive(Debug, Clone)]
+pub enum Chromosome {
+    Canonical(String),
+    /// Versioned GenBank accession of the form of a GRCh38 alternate locus or scaffold, which is
+    /// not checked against the contig list of the assembly
+    Unlisted(String),
+    /// Name not known for the assembly, kept as given except for the `chr` prefix
+    Unknown(String),
+}
+
+impl Chromosome {
+    pub fn new<S: AsRef<str>>(name: S, assembly: &Assembly) -> Self {
+        let name = name.as_ref().trim();
+
+        match canonicalize(name, assembly) {
+            Some(Contig::Listed(x)) => Chromosome::Canonical(x),
+            Some(Contig::Unlisted(x)) => Chromosome::Unlisted(x),
+            None => Chromosome::Unknown(strip_prefix(name).to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Chromosome::Canonical(x) | Chromosome::Unlisted(x) | Chromosome::Unknown(x) => x,
+        }
+    }
+
+    /// Name to match sequences of assembly files by, `None` if unknown
+    pub fn resolved(&self) -> Option<&str> {
+        match self {
+            Chromosome::Canonical(x) | Chromosome::Unlisted(x) => Some(x),
+            Chromosome::Unknown(_) => None,
+        }
+    }
+}
+
+impl Display for Chromosome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+enum Contig {
+    Listed(String),
+    Unlisted(String),
+}
+
+fn strip_prefix(name: &str) -> &str {
+    match name.get(..3) {
+        Some(x) if x.eq_ignore_ascii_case("chr") => &name[3..],
+        _ => name,
+    }
+}
+
+fn canonicalize(name: &str, assembly: &Assembly) -> Option<Contig> {
+    if let Some(x) = primary(strip_prefix(name)) {
+        return Some(Contig::Listed(x));
+    }
+
+    if let Some(caps) = regex!(r"^NC_0000(\d{2})\.(\d+)$").captures(name) {
+        let n: usize = caps[1].parse().ok()?;
+        let version: u32 = caps[2].parse().ok()?;
+
+        let expected = REFSEQ_VERSIONS_GRCH37.get(n.checked_sub(1)?)?
+            + match assembly {
+                Assembly::GRCh37 => 0,
+                Assembly::GRCh38 => 1,
+            };
+
+        return (version == expected).then(|| Contig::Listed(PRIMARY[n - 1].to_string()));
+    }
+
+    // rCRS only; NC_001807.4, the hg19 chrM, is a different sequence and is left unknown
+    if name == "NC_012920.1" {
+        return Some(Contig::Listed("MT".to_string()));
+    }
+
+    if let Assembly::GRCh37 = assembly {
+        if let Some((_, x)) = UCSC_HAPLOTYPES_GRCH37.iter().find(|(x, _)| *x == name) {
+            return Some(Contig::Listed(x.to_string()));
+        }
+    }
+
+    // UCSC contigs: chrUn_KI270302v1, chr1_KI270706v1_random, chr6_GL000250v2_alt, chrUn_gl000220
+    let accession =
+        match regex!(r"^chr(Un|\d{1,2}|X|Y)_([A-Za-z]{2}\d{6})(v\d+)?(_random|_alt|_fix)?$")
+            .captures(name)
+        {
+            Some(caps) => {
+                if (&caps[1] == "Un") == caps.get(4).is_some() {
+                    return None;
+                }
+                format!(
+                    "{}.{}",
+                    caps[2].to_uppercase(),
+                    caps.get(3).map_or("1", |x| &x.as_str()[1..])
+                )
+            }
+            None => name.to_string(),
+        };
+
+    contig(&accession, assembly)
+}
+
+fn primary(name: &str) -> Option<String> {
+    let name = match name.to_uppercase().as_str() {
+        "M" | "MT" => "MT".to_string(),
+        x => x.trim_start_matches('0').to_string(),
+    };
+
+    PRIMARY.contains(&name.as_str()).then_some(name)
+}
+
+/// Validate a versioned GenBank accession of an alternate locus or a scaffold
+///
+/// GRCh37 contigs are checked against the GL000191.1 - GL000258.1 range, while GRCh38 contigs
+/// including patches are only checked for the form of their accession.
+fn contig(accession: &str, assembly: &Assembly) -> Option<Contig> {
+    match assembly {
+        Assembly::GRCh37 => regex!(r"^GL000(19[1-9]|2[0-4]\d|25[0-8])\.1$")
+            .is_match(accession)
+            .then(|| Contig::Listed(accession.to_string())),
+        Assembly::GRCh38 => regex!(r"^(GL|KI|JH|KN|KQ|KV|KZ|ML|MU)\d{6}\.\d+$")
+            .is_match(accession)
+            .then(|| Contig::Unlisted(accession.to_string())),
+    }
+}
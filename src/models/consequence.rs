@@ -0,0 +1,445 @@
+use crate::models::annotation::{Strand, Transcript};
+use crate::models::reference::Reference;
+use crate::models::regex;
+use crate::VCFInt;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// Sequence Ontology consequence terms, ordered from the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Term {
+    SpliceAcceptor,
+    SpliceDonor,
+    StopGained,
+    Frameshift,
+    StopLost,
+    StartLost,
+    InframeInsertion,
+    InframeDeletion,
+    Missense,
+    SpliceRegion,
+    Synonymous,
+    CodingSequence,
+    FivePrimeUTR,
+    ThreePrimeUTR,
+    NonCodingTranscriptExon,
+    Intron,
+}
+
+impl Term {
+    pub fn id(&self) -> &'static str {
+        match self {
+            Term::SpliceAcceptor => "SO_0001574",
+            Term::SpliceDonor => "SO_0001575",
+            Term::StopGained => "SO_0001587",
+            Term::Frameshift => "SO_0001589",
+            Term::StopLost => "SO_0001578",
+            Term::StartLost => "SO_0002012",
+            Term::InframeInsertion => "SO_0001821",
+            Term::InframeDeletion => "SO_0001822",
+            Term::Missense => "SO_0001583",
+            Term::SpliceRegion => "SO_0001630",
+            Term::Synonymous => "SO_0001819",
+            Term::CodingSequence => "SO_0001580",
+            Term::FivePrimeUTR => "SO_0001623",
+            Term::ThreePrimeUTR => "SO_0001624",
+            Term::NonCodingTranscriptExon => "SO_0001792",
+            Term::Intron => "SO_0001627",
+        }
+    }
+}
+
+/// A variant allele in genomic coordinates
+///
+/// Deletions, substitutions and delins replace `[start, start + len(reference) - 1]`,
+/// insertions (no reference) insert `alternate` between `start` and `start + 1`.
+pub struct Allele<'a> {
+    pub chromosome: &'a str,
+    pub start: VCFInt,
+    pub reference: Option<&'a str>,
+    pub alternate: Option<&'a str>,
+}
+
+impl Allele<'_> {
+    fn is_insertion(&self) -> bool {
+        self.reference.is_none()
+    }
+
+    /// Closed interval of the affected bases; for an insertion its two flanking bases
+    fn span(&self) -> (VCFInt, VCFInt) {
+        match self.reference {
+            Some(r) => (self.start, self.start + r.len().max(1) as VCFInt - 1),
+            None => (self.start, self.start + 1),
+        }
+    }
+
+    fn touches(&self, (start, end): (VCFInt, VCFInt)) -> bool {
+        let (lo, hi) = self.span();
+        start <= end && start <= hi && lo <= end
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AminoAcidChange {
+    Substitution(u8),
+    Synonymous,
+    Frameshift,
+}
+
+/// A single amino acid change, e.g. `p.Arg1699Gln`
+#[derive(Debug, Clone)]
+pub struct ProteinChange {
+    reference: u8,
+    position: usize,
+    change: Option<AminoAcidChange>,
+}
+
+impl ProteinChange {
+    /// Parse one-letter or three-letter notation, e.g. `p.Arg1699Gln`, `R1699Q`, `p.(Gln12Ter)`, `p.Lys45fs`
+    pub fn parse<S: AsRef<str>>(s: S) -> Option<Self> {
+        let caps = regex!(
+            r"^(?:p\.)?\(?([A-Z][a-z]{2}|[A-Z*])(\d+)([A-Z][a-z]{2}|[A-Z*]|=|fs)?(\S*?)\)?$"
+        )
+        .captures(s.as_ref().trim())?;
+
+        Some(ProteinChange {
+            reference: amino_acid(&caps[1])?,
+            position: caps[2].parse().ok()?,
+            // a frameshift may name the first changed residue, e.g. `p.Lys45ArgfsTer3`
+            change: match caps.get(3).map(|x| x.as_str()) {
+                _ if caps[4].starts_with("fs") => Some(AminoAcidChange::Frameshift),
+                None => None,
+                Some("=") => Some(AminoAcidChange::Synonymous),
+                Some("fs") => Some(AminoAcidChange::Frameshift),
+                Some(x) => Some(AminoAcidChange::Substitution(amino_acid(x)?)),
+            },
+        })
+    }
+
+    /// Whether `other` describes the same change; a missing change in either matches any
+    pub fn matches(&self, other: &ProteinChange) -> bool {
+        self.reference == other.reference
+            && self.position == other.position
+            && match (&self.change, &other.change) {
+                (Some(x), Some(y)) => x == y,
+                _ => true,
+            }
+    }
+}
+
+impl Display for ProteinChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p.{}{}", three_letter(self.reference), self.position)?;
+        match self.change {
+            None => Ok(()),
+            Some(AminoAcidChange::Substitution(x)) => f.write_str(three_letter(x)),
+            Some(AminoAcidChange::Synonymous) => f.write_str("="),
+            Some(AminoAcidChange::Frameshift) => f.write_str("fs"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Prediction {
+    pub terms: BTreeSet<Term>,
+    pub protein_change: Option<ProteinChange>,
+}
+
+/// Predict the consequences of an allele on a transcript
+pub fn predict(
+    transcript: &Transcript,
+    allele: &Allele,
+    reference: &Reference,
+) -> io::Result<Prediction> {
+    let mut prediction = Prediction::default();
+    let terms = &mut prediction.terms;
+    let forward = transcript.strand == Strand::Forward;
+
+    for w in transcript.exons.windows(2) {
+        let (start, end) = (w[0].1 + 1, w[1].0 - 1);
+        if start > end {
+            continue;
+        }
+
+        let (first, last) = ((start, start + 1), (end - 1, end));
+        let (donor, acceptor) = if forward {
+            (first, last)
+        } else {
+            (last, first)
+        };
+        if allele.touches(donor) {
+            terms.insert(Term::SpliceDonor);
+        }
+        if allele.touches(acceptor) {
+            terms.insert(Term::SpliceAcceptor);
+        }
+        if [
+            (start - 3, start - 1),
+            (end + 1, end + 3),
+            (start + 2, (start + 7).min(end)),
+            ((end - 7).max(start), end - 2),
+        ]
+        .into_iter()
+        .any(|x| allele.touches(x))
+        {
+            terms.insert(Term::SpliceRegion);
+        }
+        if allele.touches((start, end)) {
+            terms.insert(Term::Intron);
+        }
+    }
+
+    if !transcript.exons.iter().any(|&x| allele.touches(x)) {
+        return Ok(prediction);
+    }
+
+    let (cds_start, cds_end) = match (transcript.cds.first(), transcript.cds.last()) {
+        (Some(first), Some(last)) => (first.0, last.1),
+        _ => {
+            terms.insert(Term::NonCodingTranscriptExon);
+            return Ok(prediction);
+        }
+    };
+
+    for &(start, end) in &transcript.exons {
+        if allele.touches((start, end.min(cds_start - 1))) {
+            terms.insert(if forward {
+                Term::FivePrimeUTR
+            } else {
+                Term::ThreePrimeUTR
+            });
+        }
+        if allele.touches((start.max(cds_end + 1), end)) {
+            terms.insert(if forward {
+                Term::ThreePrimeUTR
+            } else {
+                Term::FivePrimeUTR
+            });
+        }
+    }
+
+    if transcript.cds.iter().any(|&x| allele.touches(x)) {
+        match predict_coding(transcript, allele, reference)? {
+            Some((coding, protein_change)) => {
+                terms.extend(coding);
+                prediction.protein_change = protein_change;
+            }
+            None => {
+                terms.insert(Term::CodingSequence);
+            }
+        }
+    }
+
+    Ok(prediction)
+}
+
+/// Translate the change in the coding sequence; `None` if it cannot be resolved to CDS coordinates
+fn predict_coding(
+    transcript: &Transcript,
+    allele: &Allele,
+    reference: &Reference,
+) -> io::Result<Option<(Vec<Term>, Option<ProteinChange>)>> {
+    let forward = transcript.strand == Strand::Forward;
+    let mito = allele.chromosome == "MT";
+
+    let mut cds = Vec::new();
+    for &(start, end) in &transcript.cds {
+        match reference.fetch(allele.chromosome, start, end)? {
+            Some(x) => cds.extend(x),
+            None => return Ok(None),
+        }
+    }
+    if !forward {
+        cds = reverse_complement(&cds);
+    }
+    if cds.len() % 3 != 0 {
+        return Ok(None);
+    }
+
+    let index = |position: VCFInt| -> Option<usize> {
+        let mut offset = 0;
+        for &(start, end) in &transcript.cds {
+            if start <= position && position <= end {
+                let i = offset + (position - start) as usize;
+                return Some(if forward { i } else { cds.len() - 1 - i });
+            }
+            offset += (end - start + 1) as usize;
+        }
+        None
+    };
+
+    let (lo, hi) = allele.span();
+    let (i, j) = match (index(lo), index(hi)) {
+        (Some(i), Some(j)) if i.abs_diff(j) == (hi - lo) as usize => (i.min(j), i.max(j)),
+        _ => return Ok(None),
+    };
+
+    let alternate = allele.alternate.unwrap_or_default().as_bytes();
+    let alternate = if forward {
+        alternate.to_ascii_uppercase()
+    } else {
+        reverse_complement(alternate)
+    };
+
+    // bases of the CDS [begin, end) replaced by the alternate allele
+    let (begin, end) = if allele.is_insertion() {
+        (j, j)
+    } else {
+        (i, j + 1)
+    };
+
+    let mut mutated = cds[..begin].to_vec();
+    mutated.extend(&alternate);
+    mutated.extend(&cds[end..]);
+
+    let codon = |seq: &[u8], k: usize| seq.get(k * 3..k * 3 + 3).map(|x| translate(x, mito));
+    let first = begin / 3;
+    let reference_aa = match codon(&cds, first) {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+
+    let mut terms = Vec::new();
+    let removed = end - begin;
+
+    if (alternate.len() as isize - removed as isize) % 3 != 0 {
+        terms.push(Term::Frameshift);
+        return Ok(Some((
+            terms,
+            Some(ProteinChange {
+                reference: reference_aa,
+                position: first + 1,
+                change: Some(AminoAcidChange::Frameshift),
+            }),
+        )));
+    }
+
+    let last = (begin + alternate.len().max(removed))
+        .saturating_sub(1)
+        .max(begin)
+        / 3;
+    let mut changes = Vec::new();
+    for k in first..=last {
+        if let (Some(r), Some(a)) = (codon(&cds, k), codon(&mutated, k)) {
+            if r != a {
+                changes.push((k, r, a));
+            }
+        }
+    }
+
+    if alternate.len() != removed {
+        terms.push(if alternate.len() > removed {
+            Term::InframeInsertion
+        } else {
+            Term::InframeDeletion
+        });
+    }
+    for &(k, r, a) in &changes {
+        if k == 0 && r == b'M' {
+            terms.push(Term::StartLost);
+        } else if r == b'*' {
+            terms.push(Term::StopLost);
+        } else if a == b'*' {
+            terms.push(Term::StopGained);
+        } else if alternate.len() == removed {
+            terms.push(Term::Missense);
+        }
+    }
+    if terms.is_empty() {
+        terms.push(Term::Synonymous);
+    }
+
+    let protein_change = match (alternate.len() == removed, changes.as_slice()) {
+        (true, []) => Some(ProteinChange {
+            reference: reference_aa,
+            position: first + 1,
+            change: Some(AminoAcidChange::Synonymous),
+        }),
+        (true, [(k, r, a)]) => Some(ProteinChange {
+            reference: *r,
+            position: k + 1,
+            change: Some(AminoAcidChange::Substitution(*a)),
+        }),
+        _ => None,
+    };
+
+    Ok(Some((terms, protein_change)))
+}
+
+fn reverse_complement(seq: &[u8]) -> Vec<u8> {
+    seq.iter()
+        .rev()
+        .map(|x| match x.to_ascii_uppercase() {
+            b'A' => b'T',
+            b'C' => b'G',
+            b'G' => b'C',
+            b'T' => b'A',
+            _ => b'N',
+        })
+        .collect()
+}
+
+/// Translate a codon with the standard or the vertebrate mitochondrial code
+fn translate(codon: &[u8], mito: bool) -> u8 {
+    const BASES: &[u8; 4] = b"TCAG";
+    const STANDARD: &[u8; 64] = b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG";
+    const MITOCHONDRIAL: &[u8; 64] =
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG";
+
+    let mut i = 0;
+    for x in codon {
+        match BASES.iter().position(|b| b == x) {
+            Some(p) => i = i * 4 + p,
+            None => return b'X',
+        }
+    }
+
+    if mito {
+        MITOCHONDRIAL[i]
+    } else {
+        STANDARD[i]
+    }
+}
+
+const AMINO_ACIDS: [(u8, &str); 22] = [
+    (b'A', "Ala"),
+    (b'R', "Arg"),
+    (b'N', "Asn"),
+    (b'D', "Asp"),
+    (b'C', "Cys"),
+    (b'Q', "Gln"),
+    (b'E', "Glu"),
+    (b'G', "Gly"),
+    (b'H', "His"),
+    (b'I', "Ile"),
+    (b'L', "Leu"),
+    (b'K', "Lys"),
+    (b'M', "Met"),
+    (b'F', "Phe"),
+    (b'P', "Pro"),
+    (b'S', "Ser"),
+    (b'T', "Thr"),
+    (b'W', "Trp"),
+    (b'Y', "Tyr"),
+    (b'V', "Val"),
+    (b'*', "Ter"),
+    (b'X', "Xaa"),
+];
+
+fn three_letter(aa: u8) -> &'static str {
+    AMINO_ACIDS
+        .iter()
+        .find(|(x, _)| *x == aa)
+        .map_or("Xaa", |(_, x)| x)
+}
+
+fn amino_acid(s: &str) -> Option<u8> {
+    match s.as_bytes() {
+        [x] => Some(*x),
+        _ => AMINO_ACIDS
+            .iter()
+            .find(|(_, x)| x.eq_ignore_ascii_case(s))
+            .map(|(x, _)| *x),
+    }
+}
@@ -0,0 +1,73 @@
+use crate::models::chromosome::Chromosome;
+use crate::{reader, Assembly, VCFInt};
+use csv::ReaderBuilder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A line of UCSC cytoBand.txt
+#[derive(Debug, Deserialize)]
+pub struct Definition {
+    pub chrom: String,
+    /// 0-based
+    pub chrom_start: VCFInt,
+    /// 0-based, exclusive
+    pub chrom_end: VCFInt,
+    pub name: String,
+    pub gie_stain: String,
+}
+
+pub struct CytoBands {
+    bands: HashMap<String, Vec<Definition>>,
+}
+
+impl CytoBands {
+    pub fn from_path<P: AsRef<Path>>(path: P, assembly: &Assembly) -> Result<Self, String> {
+        let mut bands: HashMap<String, Vec<Definition>> = HashMap::new();
+
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(b'\t')
+            .from_reader(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for result in reader.deserialize::<Definition>() {
+            let row = result.map_err(|e| err(&e))?;
+
+            if row.name.is_empty() {
+                continue;
+            }
+
+            if let Some(chr) = Chromosome::new(&row.chrom, assembly).resolved() {
+                bands.entry(chr.to_string()).or_default().push(row);
+            }
+        }
+
+        for v in bands.values_mut() {
+            v.sort_by_key(|x| x.chrom_start);
+        }
+
+        Ok(CytoBands { bands })
+    }
+
+    /// Bands overlapping the 1-based closed interval `[start, end]`
+    pub fn find(&self, chromosome: &Chromosome, start: VCFInt, end: VCFInt) -> Vec<&Definition> {
+        let (start, end) = (start.min(end), start.max(end));
+
+        self.bands
+            .get(chromosome.name())
+            .map(|v| {
+                v.iter()
+                    .filter(|x| x.chrom_start < end && start <= x.chrom_end)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Definition)> {
+        self.bands
+            .iter()
+            .flat_map(|(chr, v)| v.iter().map(move |x| (chr, x)))
+    }
+}
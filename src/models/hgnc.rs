@@ -1,7 +1,7 @@
 use crate::models::regex;
 use csv::ReaderBuilder;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 #[derive(Debug, Deserialize)]
@@ -9,15 +9,108 @@ pub struct Definition {
     pub hgnc_id: String,
     pub symbol: String,
     pub alias_symbol: String,
+    pub prev_symbol: Option<String>,
+    pub name: Option<String>,
+    pub locus_group: Option<String>,
+    /// Cytogenetic location, e.g. 17q21.31
+    pub location: Option<String>,
+    pub entrez_id: Option<String>,
+    pub ensembl_gene_id: Option<String>,
+    /// Pipe-separated list
+    pub omim_id: Option<String>,
+    /// Pipe-separated list
+    pub uniprot_ids: Option<String>,
+}
+
+/// A line of withdrawn.txt
+#[derive(Debug, Deserialize)]
+pub struct Withdrawal {
+    #[serde(rename(deserialize = "HGNC_ID"))]
+    pub hgnc_id: String,
+    #[serde(rename(deserialize = "WITHDRAWN_SYMBOL"))]
+    pub withdrawn_symbol: String,
+    /// Comma-separated list of `HGNC:ID|SYMBOL|STATUS`
+    #[serde(rename(deserialize = "MERGED_INTO_REPORT(S)"))]
+    pub merged_into_reports: Option<String>,
+}
+
+/// How a submitted symbol maps to an HGNC ID
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    Approved(String),
+    Previous(String),
+    Alias(String),
+    /// Withdrawn symbol or ID merged into a single current entry
+    Merged(String),
+    /// Previous or alias symbol of several entries, or withdrawn and split into several entries
+    Ambiguous(Vec<String>),
+    /// Withdrawn without successor
+    Withdrawn,
+    NotFound,
+}
+
+impl Resolution {
+    pub fn id(&self) -> Option<&String> {
+        match self {
+            Resolution::Approved(x)
+            | Resolution::Previous(x)
+            | Resolution::Alias(x)
+            | Resolution::Merged(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        match self {
+            Resolution::Approved(_) => "approved",
+            Resolution::Previous(_) => "previous",
+            Resolution::Alias(_) => "alias",
+            Resolution::Merged(_) => "merged",
+            Resolution::Ambiguous(_) => "ambiguous",
+            Resolution::Withdrawn => "withdrawn",
+            Resolution::NotFound => "not_found",
+        }
+    }
 }
 
 pub struct HGNC {
-    definitions: HashMap<String, String>,
+    symbols: HashMap<String, String>,
+    previous: HashMap<String, Vec<String>>,
+    aliases: HashMap<String, Vec<String>>,
+    /// Withdrawn symbols and IDs to the IDs they were merged into
+    withdrawn: HashMap<String, Vec<String>>,
+    entries: HashMap<String, Definition>,
+}
+
+fn split(list: &str) -> impl Iterator<Item = &str> {
+    let list = regex!(r#"^"?(.*?)"?$"#)
+        .captures(list)
+        .and_then(|caps| caps.get(1))
+        .map_or(list, |x| x.as_str());
+
+    list.split('|').map(|x| x.trim()).filter(|x| !x.is_empty())
+}
+
+fn push(map: &mut HashMap<String, Vec<String>>, key: &str, id: &str) {
+    let v = map.entry(key.to_string()).or_default();
+    if !v.iter().any(|x| x == id) {
+        v.push(id.to_string());
+    }
 }
 
 impl HGNC {
-    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-        let mut definitions = HashMap::new();
+    /// Load hgnc_complete_set.txt and optionally withdrawn.txt
+    pub fn from_path<P: AsRef<Path>, Q: AsRef<Path>>(
+        path: P,
+        withdrawn: Option<Q>,
+    ) -> Result<Self, String> {
+        let mut hgnc = HGNC {
+            symbols: HashMap::new(),
+            previous: HashMap::new(),
+            aliases: HashMap::new(),
+            withdrawn: HashMap::new(),
+            entries: HashMap::new(),
+        };
 
         let mut reader = ReaderBuilder::new()
             .has_headers(true)
@@ -29,21 +122,121 @@ impl HGNC {
             let row = result.map_err(|e| format!("{}: {}", path.as_ref().to_string_lossy(), e))?;
 
             if let Some(caps) = regex!(r"HGNC:(\d+)").captures(row.hgnc_id.as_str()) {
-                let id = &caps[1];
-                definitions.insert(row.symbol.clone(), id.to_string());
+                let id = caps[1].to_string();
+                hgnc.symbols.insert(row.symbol.clone(), id.clone());
+
+                for x in split(&row.alias_symbol) {
+                    push(&mut hgnc.aliases, x, &id);
+                }
+                for x in row.prev_symbol.iter().flat_map(|x| split(x)) {
+                    push(&mut hgnc.previous, x, &id);
+                }
+
+                hgnc.entries.insert(id, row);
+            }
+        }
+
+        if let Some(path) = withdrawn {
+            let mut reader = ReaderBuilder::new()
+                .has_headers(true)
+                .delimiter(b'\t')
+                .from_path(path.as_ref())
+                .map_err(|e| format!("{}: {}", path.as_ref().to_string_lossy(), e))?;
+
+            for result in reader.deserialize::<Withdrawal>() {
+                let row =
+                    result.map_err(|e| format!("{}: {}", path.as_ref().to_string_lossy(), e))?;
 
-                if let Some(caps) = regex!(r#"^"?(.*)"?$"#).captures(row.alias_symbol.as_str()) {
-                    for x in caps[1].split("|") {
-                        definitions.insert(x.to_string(), id.to_string());
+                let targets: Vec<String> = row
+                    .merged_into_reports
+                    .iter()
+                    .flat_map(|x| x.split(','))
+                    .filter_map(|x| regex!(r"HGNC:(\d+)").captures(x))
+                    .map(|caps| caps[1].to_string())
+                    .collect();
+
+                let symbol = row.withdrawn_symbol.trim_end_matches("~withdrawn");
+                let mut keys = vec![symbol.to_string()];
+                if let Some(caps) = regex!(r"HGNC:(\d+)").captures(&row.hgnc_id) {
+                    keys.push(format!("HGNC:{}", &caps[1]));
+                }
+                for key in keys {
+                    let v = hgnc.withdrawn.entry(key).or_default();
+                    for x in &targets {
+                        if !v.contains(x) {
+                            v.push(x.clone());
+                        }
                     }
-                };
+                }
+            }
+
+            // follow targets withdrawn in turn to their approved entries
+            let resolved: HashMap<String, Vec<String>> = hgnc
+                .withdrawn
+                .iter()
+                .map(|(k, v)| (k.clone(), hgnc.approved_targets(v)))
+                .collect();
+            hgnc.withdrawn = resolved;
+        }
+
+        Ok(hgnc)
+    }
+
+    /// Approved entries of the targets of a withdrawal, through chains of withdrawn IDs
+    fn approved_targets(&self, targets: &[String]) -> Vec<String> {
+        let mut approved = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut stack: Vec<&str> = targets.iter().rev().map(|x| x.as_str()).collect();
+
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            if self.entries.contains_key(id) {
+                approved.push(id.to_string());
+            } else if let Some(x) = self.withdrawn.get(&format!("HGNC:{}", id)) {
+                stack.extend(x.iter().rev().map(|x| x.as_str()));
             }
         }
 
-        Ok(HGNC { definitions })
+        approved
+    }
+
+    /// Resolve an approved, previous, alias or withdrawn symbol, or an `HGNC:ID`
+    pub fn resolve<T: AsRef<str>>(&self, symbol: T) -> Resolution {
+        let symbol = symbol.as_ref().trim();
+
+        if let Some(caps) = regex!(r"^HGNC:(\d+)$").captures(symbol) {
+            if self.entries.contains_key(&caps[1]) {
+                return Resolution::Approved(caps[1].to_string());
+            }
+        } else if let Some(id) = self.symbols.get(symbol) {
+            return Resolution::Approved(id.to_owned());
+        }
+
+        for (map, f) in [
+            (
+                &self.previous,
+                Resolution::Previous as fn(String) -> Resolution,
+            ),
+            (&self.aliases, Resolution::Alias),
+        ] {
+            match map.get(symbol).map(|x| x.as_slice()) {
+                Some([id]) => return f(id.to_owned()),
+                Some(ids) if ids.len() > 1 => return Resolution::Ambiguous(ids.to_vec()),
+                _ => {}
+            }
+        }
+
+        match self.withdrawn.get(symbol).map(|x| x.as_slice()) {
+            Some([]) => Resolution::Withdrawn,
+            Some([id]) => Resolution::Merged(id.to_owned()),
+            Some(ids) => Resolution::Ambiguous(ids.to_vec()),
+            None => Resolution::NotFound,
+        }
     }
 
-    pub fn find<T: AsRef<str>>(&self, symbol: T) -> Option<&String> {
-        self.definitions.get(symbol.as_ref())
+    pub fn entry<T: AsRef<str>>(&self, id: T) -> Option<&Definition> {
+        self.entries.get(id.as_ref())
     }
 }
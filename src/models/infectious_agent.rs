@@ -0,0 +1,119 @@
+use crate::models::input::Row;
+use crate::reader;
+use regex::{Regex, RegexBuilder};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Built-in sub disease areas, NCBI Taxonomy IDs and patterns of the disease names
+const DEFAULT_INFECTIOUS_AGENTS: [(&str, &str, Option<&str>); 4] = [
+    // Human immunodeficiency virus
+    ("HIV", "12721", None),
+    // Human T-lymphotropic virus 1
+    ("HTLV1", "11908", None),
+    // Hepatitis B virus
+    ("Hepatitis", "10407", Some(r"hepatitis B|\bHBV\b")),
+    // Hepatitis C virus
+    ("Hepatitis", "11103", Some(r"hepatitis C|\bHCV\b")),
+];
+
+/// NCBI Taxonomy ID of the pathogen of a sub disease area, linked when the area alone names the
+/// pathogen or the disease name matches `pattern`
+#[derive(Debug)]
+pub struct InfectiousAgent {
+    area: String,
+    taxon: String,
+    pattern: Option<Regex>,
+}
+
+#[derive(Debug)]
+pub struct InfectiousAgents {
+    agents: Vec<InfectiousAgent>,
+}
+
+impl InfectiousAgent {
+    fn new(area: &str, taxon: &str, pattern: Option<&str>) -> Result<Self, regex::Error> {
+        Ok(InfectiousAgent {
+            area: area.to_string(),
+            taxon: taxon.to_string(),
+            pattern: match pattern {
+                Some(x) => Some(RegexBuilder::new(x).case_insensitive(true).build()?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl Default for InfectiousAgents {
+    fn default() -> Self {
+        InfectiousAgents {
+            agents: DEFAULT_INFECTIOUS_AGENTS
+                .iter()
+                .map(|(area, taxon, pattern)| {
+                    InfectiousAgent::new(area, taxon, *pattern).expect("valid built-in pattern")
+                })
+                .collect(),
+        }
+    }
+}
+
+impl InfectiousAgents {
+    /// Read a TSV of sub disease areas, NCBI Taxonomy IDs and optional case-insensitive patterns
+    /// of the disease names, e.g. `HIV\t12721` or `Hepatitis\t10407\thepatitis B|\bHBV\b`
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut agents = Vec::new();
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| err(&e))?;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split('\t').map(|x| x.trim());
+            let (area, taxon) = match (fields.next(), fields.next()) {
+                (Some(area), Some(taxon)) if taxon.bytes().all(|x| x.is_ascii_digit()) => {
+                    (area, taxon)
+                }
+                _ => Err(err(&format!(
+                    "expected a sub disease area and a taxon ID: {}",
+                    line
+                )))?,
+            };
+            let pattern = fields.next().filter(|x| !x.is_empty());
+
+            agents.push(InfectiousAgent::new(area, taxon, pattern).map_err(|e| err(&e))?);
+        }
+
+        Ok(InfectiousAgents { agents })
+    }
+
+    /// Replace the agents of the sub disease areas defined in `other`, and add those of others
+    pub fn merge(mut self, other: InfectiousAgents) -> Self {
+        self.agents
+            .retain(|x| !other.agents.iter().any(|y| y.area == x.area));
+        self.agents.extend(other.agents);
+        self
+    }
+
+    /// Taxon IDs of the pathogens named by the sub disease area and the disease name of the row
+    pub fn find(&self, row: &Row) -> Vec<&str> {
+        let area = match &row.disease_area_2 {
+            Some(x) => x.to_string(),
+            None => return Vec::new(),
+        };
+        let name = row.disease_name.as_deref().unwrap_or_default();
+
+        let mut taxa: Vec<&str> = self
+            .agents
+            .iter()
+            .filter(|x| x.area == area)
+            .filter(|x| x.pattern.as_ref().is_none_or(|p| p.is_match(name)))
+            .map(|x| x.taxon.as_str())
+            .collect();
+        taxa.sort_unstable();
+        taxa.dedup();
+        taxa
+    }
+}
@@ -1,14 +1,62 @@
-use crate::{Assembly, VCFInt};
+use crate::models::chromosome::Chromosome;
+use crate::models::hgnc::HGNC;
+use crate::models::output::disease::DiseaseIds;
+use crate::models::output::normalize;
+use crate::models::output::organization::RorMapping;
+use crate::models::output::provenance::Provenance;
+use crate::models::prefix_map::PrefixMaps;
+use crate::{Assembly, Normalization, VCFInt};
 use serde::{Deserialize, Serialize};
+use strum::Display;
 
 pub struct Record<'a> {
     pub assembly: &'a Assembly,
     pub row: &'a Row,
+    pub hgnc: &'a HGNC,
+    /// Maps giving the IRIs of the cross-references
+    pub prefix_maps: &'a PrefixMaps,
+    /// ROR IDs giving the IDs of the organizations and persons
+    pub ror: &'a RorMapping,
+    pub chromosome: Chromosome,
+    /// ID of the normalized DiseaseName
+    pub disease_id: Option<String>,
+    /// Position of the row in the input file, if asked for
+    pub provenance: Option<Provenance>,
 }
 
 impl<'a> Record<'a> {
-    pub fn new(assembly: &'a Assembly, row: &'a Row) -> Self {
-        Record { assembly, row }
+    pub fn new(
+        assembly: &'a Assembly,
+        row: &'a Row,
+        hgnc: &'a HGNC,
+        prefix_maps: &'a PrefixMaps,
+        ror: &'a RorMapping,
+        normalization: &[Normalization],
+        disease_ids: &DiseaseIds,
+    ) -> Self {
+        Record {
+            assembly,
+            row,
+            hgnc,
+            prefix_maps,
+            ror,
+            chromosome: Chromosome::new(&row.chr, assembly),
+            disease_id: Record::disease_name(row, normalization).map(|x| disease_ids.id(&x)),
+            provenance: None,
+        }
+    }
+
+    /// Normalized DiseaseName, if not blank
+    pub fn disease_name(row: &Row, normalization: &[Normalization]) -> Option<String> {
+        row.disease_name
+            .as_ref()
+            .map(|x| normalize(x, normalization))
+            .filter(|x| !x.is_empty())
+    }
+
+    /// Whether the row is a GWAS result rather than cases of a clinical variant
+    pub fn is_gwas(&self) -> bool {
+        matches!(self.row.data_origin, Some(DataOrigin::GWAS))
     }
 }
 
@@ -176,6 +224,7 @@ pub struct Row {
     #[serde(rename(deserialize = "ConditionIDValue"))]
     pub condition_id_value: Option<String>,
     #[serde(rename(deserialize = "PreferredConditionName"))]
+    #[allow(dead_code)]
     pub preferred_condition_name: Option<String>,
     #[serde(rename(deserialize = "DiseaseName"))]
     pub disease_name: Option<String>,
@@ -200,10 +249,11 @@ pub struct Row {
     pub submitter_institute_2: Option<String>,
     /// Comma-separated list
     #[serde(rename(deserialize = "Citation"))]
+    #[allow(dead_code)]
     pub citation: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Display)]
 pub enum ClinicalSignificance {
     Benign,
     Pathogenic,
@@ -237,7 +287,7 @@ pub enum ConditionIDType {
     Orphanet,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Display)]
 pub enum DiseaseArea1 {
     Cancer,
     Dementia,
@@ -249,7 +299,7 @@ pub enum DiseaseArea1 {
     RareIntractableDiseases,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Display)]
 pub enum DiseaseArea2 {
     Germline,
     HIV,
@@ -259,7 +309,7 @@ pub enum DiseaseArea2 {
     Somatic,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Display)]
 pub enum DataOrigin {
     #[serde(rename(deserialize = "gwas"))]
     GWAS,
@@ -267,7 +317,7 @@ pub enum DataOrigin {
     Variant,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Display)]
 pub enum AlleleOrigin {
     Germline,
     Somatic,
@@ -16,32 +16,65 @@ macro_rules! ns {
     };
 }
 
+ns!(DCAT, "dcat", "http://www.w3.org/ns/dcat#");
+ns!(DCTERMS, "dcterms", "http://purl.org/dc/terms/");
+ns!(ENSEMBL, "ensembl", "http://identifiers.org/ensembl/");
 ns!(FALDO, "faldo", "http://biohackathon.org/resource/faldo#");
 ns!(FOAF, "foaf", "http://xmlns.com/foaf/0.1/");
 ns!(GVO, "gvo", "http://genome-variation.org/");
 ns!(HCO, "hco", "http://identifiers.org/hco:");
 ns!(HGNC, "hgnc", "http://identifiers.org/hgnc:");
+ns!(NCBIGENE, "ncbigene", "http://identifiers.org/ncbigene/");
 ns!(OBO, "obo", "http://purl.obolibrary.org/obo/");
 ns!(OLO, "olo", "http://purl.org/ontology/olo/core#");
-ns!(ORG, "org", "https://www.w3.org/ns/org#");
+ns!(OMIM, "omim", "http://identifiers.org/mim/");
+ns!(ORG, "org", "http://www.w3.org/ns/org#");
+ns!(OWL, "owl", "http://www.w3.org/2002/07/owl#");
 ns!(PAV, "pav", "http://purl.org/pav/");
+ns!(PROV, "prov", "http://www.w3.org/ns/prov#");
 ns!(RDF, "rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#");
 ns!(RDFS, "rdfs", "http://www.w3.org/2000/01/rdf-schema#");
+ns!(REFSEQ, "refseq", "http://identifiers.org/refseq/");
 ns!(SIO, "sio", "http://semanticscience.org/resource/");
 ns!(SKOS, "skos", "http://www.w3.org/2004/02/skos/core#");
+ns!(SPDX, "spdx", "http://spdx.org/rdf/terms#");
+ns!(UNIPROT, "uniprot", "http://identifiers.org/uniprot/");
+ns!(VOID, "void", "http://rdfs.org/ns/void#");
+ns!(XSD, "xsd", "http://www.w3.org/2001/XMLSchema#");
 ns!(MED2RDF, "m2r", "http://med2rdf.org/ontology/med2rdf#");
+ns!(MGEND, "mgend", "http://med2rdf.org/mgend/");
+ns!(
+    MGEND_ASSOCIATION,
+    "mgend_association",
+    "http://med2rdf.org/mgend/association/"
+);
 ns!(
     MGEND_ONTOLOGY,
     "mgendo",
     "http://med2rdf.org/mgend/ontology#"
 );
 ns!(MGEND_CASE, "mgend_case", "http://med2rdf.org/mgend/case/");
+ns!(
+    MGEND_CYTOBAND,
+    "mgend_cytoband",
+    "http://med2rdf.org/mgend/cytoband/"
+);
 ns!(
     MGEND_DISEASE,
     "mgend_disease",
     "http://med2rdf.org/mgend/disease/"
 );
 ns!(MGEND_GENE, "mgend_gene", "http://med2rdf.org/mgend/gene/");
+ns!(
+    MGEND_ORGANIZATION,
+    "mgend_organization",
+    "http://med2rdf.org/mgend/organization/"
+);
+ns!(
+    MGEND_PERSON,
+    "mgend_person",
+    "http://med2rdf.org/mgend/person/"
+);
 ns!(
     MGEND_SUBMISSION,
     "mgend_submission",
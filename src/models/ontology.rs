@@ -0,0 +1,285 @@
+use crate::models::name_space::OBO;
+use crate::models::output::normalize;
+use crate::models::regex;
+use crate::{reader, Normalization};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use strum::Display;
+
+/// A class of MONDO, HPO, Orphanet or any other OBO/OWL ontology
+#[derive(Debug)]
+pub struct Term {
+    pub iri: String,
+    pub label: String,
+    pub synonyms: Vec<(String, Scope)>,
+    pub obsolete: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    Exact,
+    Related,
+    Broad,
+    Narrow,
+}
+
+/// How a disease name matched a term, from the most to the least reliable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MatchType {
+    ExactLabel,
+    ExactSynonym,
+    NormalizedLabel,
+    NormalizedSynonym,
+    RelatedSynonym,
+    /// The name is broader than the term
+    BroadSynonym,
+    /// The name is narrower than the term
+    NarrowSynonym,
+}
+
+impl MatchType {
+    /// Whether the match is good enough for `skos:exactMatch` rather than `skos:closeMatch`
+    pub fn is_exact(&self) -> bool {
+        matches!(self, MatchType::ExactLabel | MatchType::ExactSynonym)
+    }
+}
+
+/// Labels and synonyms of the terms of local ontology files
+pub struct Ontologies {
+    terms: Vec<Term>,
+    exact: HashMap<String, Vec<(usize, MatchType)>>,
+    normalized: HashMap<String, Vec<(usize, MatchType)>>,
+}
+
+impl Term {
+    fn new() -> Self {
+        Term {
+            iri: String::new(),
+            label: String::new(),
+            synonyms: Vec::new(),
+            obsolete: false,
+        }
+    }
+}
+
+fn scope(s: &str) -> Scope {
+    match s.to_ascii_uppercase().as_str() {
+        "EXACT" => Scope::Exact,
+        "BROAD" => Scope::Broad,
+        "NARROW" => Scope::Narrow,
+        _ => Scope::Related,
+    }
+}
+
+/// `MONDO:0007254` to `http://purl.obolibrary.org/obo/MONDO_0007254`
+fn obo_iri(id: &str) -> String {
+    match id.split_once(':') {
+        Some((prefix, local)) if !prefix.starts_with("http") => {
+            format!("{}{}_{}", OBO.uri, prefix, local)
+        }
+        _ => id.to_string(),
+    }
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn read_obo<R: BufRead>(reader: R) -> Result<Vec<Term>, String> {
+    let mut terms = Vec::new();
+    let mut term: Option<Term> = None;
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            terms.extend(term.take());
+            if line == "[Term]" {
+                term = Some(Term::new());
+            }
+            continue;
+        }
+
+        let t = match term.as_mut() {
+            Some(x) => x,
+            None => continue,
+        };
+
+        if let Some(x) = line.strip_prefix("id:") {
+            t.iri = obo_iri(x.trim());
+        } else if let Some(x) = line.strip_prefix("name:") {
+            t.label = x.trim().to_string();
+        } else if let Some(caps) =
+            regex!(r#"^synonym:\s*"((?:[^"\\]|\\.)*)"\s+(\w+)"#).captures(line)
+        {
+            t.synonyms
+                .push((caps[1].replace("\\\"", "\""), scope(&caps[2])));
+        } else if line.starts_with("is_obsolete:") && line.ends_with("true") {
+            t.obsolete = true;
+        }
+    }
+    terms.extend(term);
+
+    Ok(terms)
+}
+
+fn read_owl<R: BufRead>(reader: R) -> Result<Vec<Term>, String> {
+    let mut terms = Vec::new();
+    let mut term: Option<Term> = None;
+    // anonymous classes nested in the current term, e.g. in owl:equivalentClass
+    let mut depth = 0;
+
+    for line in reader.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.trim();
+
+        if term.is_none() {
+            if let Some(caps) = regex!(r#"^<owl:Class rdf:about="([^"]+)"\s*>"#).captures(line) {
+                let mut t = Term::new();
+                t.iri = unescape_xml(&caps[1]);
+                term = Some(t);
+                depth = 0;
+            }
+            continue;
+        }
+        if regex!(r"^<owl:Class\b[^>]*[^/]>$|^<owl:Class>$").is_match(line) {
+            depth += 1;
+            continue;
+        }
+        if line.starts_with("</owl:Class>") {
+            if depth == 0 {
+                terms.extend(term.take());
+            } else {
+                depth -= 1;
+            }
+            continue;
+        }
+
+        let t = match term.as_mut() {
+            Some(x) if depth == 0 => x,
+            _ => continue,
+        };
+
+        if let Some(caps) = regex!(r"^<([\w:]+)[^>]*>([^<]*)</([\w:]+)>").captures(line) {
+            let value = unescape_xml(&caps[2]);
+            match &caps[1] {
+                "rdfs:label" => t.label = value,
+                "oboInOwl:hasExactSynonym" => t.synonyms.push((value, Scope::Exact)),
+                "oboInOwl:hasRelatedSynonym" => t.synonyms.push((value, Scope::Related)),
+                "oboInOwl:hasBroadSynonym" => t.synonyms.push((value, Scope::Broad)),
+                "oboInOwl:hasNarrowSynonym" => t.synonyms.push((value, Scope::Narrow)),
+                "owl:deprecated" => t.obsolete = value == "true",
+                _ => {}
+            }
+        }
+    }
+
+    Ok(terms)
+}
+
+/// Read the terms of an `*.obo` file or RDF/XML `*.owl` file, optionally gzipped
+pub fn read_terms<P: AsRef<Path>>(path: P) -> Result<Vec<Term>, String> {
+    let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+    let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+    if reader::extension(path.as_ref()).is_some_and(|x| x == "obo") {
+        read_obo(reader)
+    } else {
+        read_owl(reader)
+    }
+    .map_err(|e| err(&e))
+}
+
+impl Ontologies {
+    /// Load `*.obo` files and RDF/XML `*.owl` files, optionally gzipped
+    pub fn from_paths<P: AsRef<Path>>(
+        paths: &[P],
+        normalization: &[Normalization],
+    ) -> Result<Self, String> {
+        let mut ontologies = Ontologies {
+            terms: Vec::new(),
+            exact: HashMap::new(),
+            normalized: HashMap::new(),
+        };
+
+        for path in paths {
+            ontologies.terms.extend(
+                read_terms(path)?
+                    .into_iter()
+                    .filter(|x| !x.obsolete && !x.iri.is_empty()),
+            );
+        }
+
+        for (i, term) in ontologies.terms.iter().enumerate() {
+            let names = std::iter::once((&term.label, None))
+                .chain(term.synonyms.iter().map(|(x, s)| (x, Some(*s))))
+                .filter(|(x, _)| !x.is_empty());
+
+            for (name, scope) in names {
+                let (exact, normalized) = match scope {
+                    None => (MatchType::ExactLabel, MatchType::NormalizedLabel),
+                    Some(Scope::Exact) => (MatchType::ExactSynonym, MatchType::NormalizedSynonym),
+                    Some(Scope::Related) => (MatchType::RelatedSynonym, MatchType::RelatedSynonym),
+                    Some(Scope::Broad) => (MatchType::BroadSynonym, MatchType::BroadSynonym),
+                    Some(Scope::Narrow) => (MatchType::NarrowSynonym, MatchType::NarrowSynonym),
+                };
+                ontologies
+                    .exact
+                    .entry(name.to_string())
+                    .or_default()
+                    .push((i, exact));
+                ontologies
+                    .normalized
+                    .entry(normalize(name, normalization))
+                    .or_default()
+                    .push((i, normalized));
+            }
+        }
+
+        Ok(ontologies)
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Terms matching any of the names, with the best match type of each term.
+    /// Normalized matches are only tried if no name matches verbatim.
+    pub fn find<S: AsRef<str>>(
+        &self,
+        names: &[S],
+        normalization: &[Normalization],
+    ) -> Vec<(&Term, MatchType)> {
+        let lookup = |map: &HashMap<String, Vec<(usize, MatchType)>>,
+                      f: &dyn Fn(&str) -> String| {
+            let mut found: Vec<(usize, MatchType)> = names
+                .iter()
+                .filter_map(|x| map.get(&f(x.as_ref())))
+                .flatten()
+                .copied()
+                .collect();
+            found.sort_unstable();
+            found.dedup_by_key(|x| x.0);
+            found
+        };
+
+        let mut found = lookup(&self.exact, &|x| x.to_string());
+        if found.is_empty() {
+            found = lookup(&self.normalized, &|x| normalize(x, normalization));
+        }
+
+        found
+            .into_iter()
+            .map(|(i, t)| (&self.terms[i], t))
+            .collect()
+    }
+}
@@ -1,13 +1,64 @@
-use serde::{Serialize, Serializer};
-use std::fmt::{Display, Formatter};
+use crate::format::turtle::ToTurtle;
+use crate::models::input::{
+    AlleleOrigin, ClinicalSignificance, CodeType, ConditionIDType, DataOrigin, DiseaseArea1,
+    DiseaseArea2, Record,
+};
+use crate::models::name_space::{MGEND_ONTOLOGY, RDFS};
+use crate::models::prefix_map::PrefixMaps;
+use crate::models::regex;
+use crate::models::vocabulary::{Validation, Vocabularies};
+use crate::Normalization;
+use serde::Serialize;
+use std::fmt::Display;
+use std::io;
+use std::io::Write;
+use unicode_normalization::UnicodeNormalization;
 
+pub mod association;
 pub mod case;
+pub mod cytoband;
+pub mod dataset;
 pub mod disease;
 pub mod gene;
+pub mod organization;
+pub mod person;
+pub mod provenance;
 pub mod submission;
 pub mod variant;
+pub mod xref;
 
-#[derive(Debug)]
+/// Enumerated value of an input column, emitted as an individual of the MGeND ontology
+pub trait Term: Display {
+    /// e.g. `mgendo:LikelyPathogenic`
+    fn term(&self) -> String {
+        format!("{}:{}", MGEND_ONTOLOGY.prefix, self)
+    }
+}
+
+impl Term for ClinicalSignificance {}
+impl Term for AlleleOrigin {}
+impl Term for DataOrigin {}
+impl Term for DiseaseArea1 {}
+impl Term for DiseaseArea2 {}
+
+/// Apply the normalization steps to a disease, organization or person name
+pub fn normalize<S: AsRef<str>>(name: S, normalization: &[Normalization]) -> String {
+    let mut name = name.as_ref().to_string();
+
+    if normalization.contains(&Normalization::NFKC) {
+        name = name.nfkc().collect();
+    }
+    if normalization.contains(&Normalization::Whitespace) {
+        name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+    }
+    if normalization.contains(&Normalization::Case) {
+        name = caseless::default_case_fold_str(&name);
+    }
+
+    name
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum XRef {
     ICD10(String),
     SnomedCt(String),
@@ -20,29 +71,241 @@ pub enum XRef {
     Orphanet(String),
 }
 
-impl Serialize for XRef {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&format!("{}", self))
+impl XRef {
+    /// Cross-references of the CodeValue and ConditionIDValue of a row, or the code system and
+    /// value of those not in the expected format
+    pub fn from_record(record: &Record) -> Vec<Result<Self, (&'static str, String)>> {
+        let r = record.row;
+        let mut xref = Vec::new();
+
+        if let (Some(typ), Some(id)) = (&r.code_type, &r.code_value) {
+            let (system, x) = match typ {
+                CodeType::ICD10 => (
+                    "ICD10",
+                    regex!(r"^[A-Z]\d{2}(\.(-|[\dA-Z]{1,4}))?$")
+                        .captures(id.trim())
+                        .map(|caps| XRef::ICD10(caps[0].to_string())),
+                ),
+                CodeType::SnomedCt => ("SNOMED CT", Some(XRef::SnomedCt(id.to_owned()))),
+            };
+            xref.push(x.ok_or((system, id.to_owned())));
+        }
+        if let (Some(typ), Some(id)) = (&r.condition_id_type, &r.condition_id_value) {
+            let (system, x) = match typ {
+                ConditionIDType::MeSH => (
+                    "MeSH",
+                    regex!(r"^(?i:MESH:)?([CD]\d+)$")
+                        .captures(id.trim())
+                        .map(|caps| XRef::MeSH(caps[1].to_string())),
+                ),
+                ConditionIDType::MedGen => (
+                    "MedGen",
+                    if let Some(caps) = regex!(r"^[CN]+\d{4,7}$").captures(id) {
+                        Some(XRef::MedGenCID(caps[0].to_string()))
+                    } else {
+                        regex!(r"^\d{4,7}$")
+                            .captures(id)
+                            .map(|caps| XRef::MedGenUID(caps[0].to_string()))
+                    },
+                ),
+                ConditionIDType::OMIM => (
+                    "OMIM",
+                    if let Some(caps) = regex!(r"^PS\d+$").captures(id) {
+                        Some(XRef::OMIMPS(caps[0].to_string()))
+                    } else {
+                        regex!(r"^\d+$")
+                            .captures(id)
+                            .map(|caps| XRef::OMIM(caps[0].to_string()))
+                    },
+                ),
+                ConditionIDType::HPO => (
+                    "HPO",
+                    regex!(r"^(?i:HP[:_])?(\d{7})$")
+                        .captures(id.trim())
+                        .map(|caps| XRef::HPO(caps[1].to_string())),
+                ),
+                ConditionIDType::Orphanet => (
+                    "Orphanet",
+                    regex!(r"^(?i:ORPHA:|Orphanet[:_])?(\d+)$")
+                        .captures(id.trim())
+                        .map(|caps| XRef::Orphanet(caps[1].to_string())),
+                ),
+            };
+            xref.push(x.ok_or((system, id.to_owned())));
+        }
+
+        xref
+    }
+
+    pub fn id(&self) -> &String {
+        match self {
+            XRef::ICD10(id)
+            | XRef::SnomedCt(id)
+            | XRef::MeSH(id)
+            | XRef::MedGenUID(id)
+            | XRef::MedGenCID(id)
+            | XRef::OMIM(id)
+            | XRef::OMIMPS(id)
+            | XRef::HPO(id)
+            | XRef::Orphanet(id) => id,
+        }
+    }
+
+    /// Prefix of the code system in the prefix map
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            XRef::ICD10(_) => "icd10",
+            XRef::SnomedCt(_) => "snomedct",
+            XRef::MeSH(_) => "mesh",
+            XRef::MedGenUID(_) => "medgen",
+            XRef::MedGenCID(_) => "medgen.cid",
+            XRef::OMIM(_) => "omim",
+            XRef::OMIMPS(_) => "omim.ps",
+            XRef::HPO(_) => "hp",
+            XRef::Orphanet(_) => "orphanet",
+        }
     }
+
+    /// IRI in the primary prefix map, or `prefix:id` if the map lacks the prefix
+    pub fn iri(&self, prefix_maps: &PrefixMaps) -> String {
+        prefix_maps
+            .primary
+            .expand(self.prefix(), self.id())
+            .unwrap_or_else(|| format!("{}:{}", self.prefix(), self.id()))
+    }
+
+    /// IRIs of the alternate prefix maps that differ from the primary one
+    pub fn alternates(&self, prefix_maps: &PrefixMaps) -> Vec<String> {
+        let iri = self.iri(prefix_maps);
+
+        let mut v: Vec<String> = prefix_maps
+            .alternates
+            .iter()
+            .filter_map(|x| x.expand(self.prefix(), self.id()))
+            .filter(|x| *x != iri)
+            .collect();
+        v.sort();
+        v.dedup();
+        v
+    }
+
+    /// Code system, shared by the identifier kinds of the same source. OMIM phenotypic series
+    /// group MIM entries rather than naming another one, so they are a system of their own.
+    pub fn system(&self) -> &'static str {
+        match self {
+            XRef::ICD10(_) => "ICD10",
+            XRef::SnomedCt(_) => "SNOMED CT",
+            XRef::MeSH(_) => "MeSH",
+            XRef::MedGenUID(_) | XRef::MedGenCID(_) => "MedGen",
+            XRef::OMIM(_) => "OMIM",
+            XRef::OMIMPS(_) => "OMIM PS",
+            XRef::HPO(_) => "HPO",
+            XRef::Orphanet(_) => "Orphanet",
+        }
+    }
+}
+
+/// Label of a code a case or association refers to, in the local vocabulary of its code system
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LabeledXRef {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    xref: String,
+    xref_label: String,
 }
 
-impl Display for XRef {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&match self {
-            XRef::ICD10(id) => format!("http://purl.bioontology.org/ontology/ICD10CM/{}", id),
-            XRef::SnomedCt(id) => format!("http://identifiers.org/snomedct/{}", id),
-            XRef::MeSH(id) => format!("http://identifiers.org/mesh/{}", id),
-            XRef::MedGenUID(id) => format!("http://identifiers.org/medgen/{}", id),
-            XRef::MedGenCID(id) => format!("http://www.ncbi.nlm.nih.gov/medgen/{}", id),
-            XRef::OMIM(id) => format!("https://omim.org/entry/{}", id),
-            XRef::OMIMPS(id) => format!("https://omim.org/phenotypicSeries/{}", id),
-            XRef::HPO(id) => format!("http://purl.obolibrary.org/obo/HP_{}", id),
-            XRef::Orphanet(id) => {
-                format!("http://purl.obolibrary.org/obo/Orphanet_{}", id)
+impl LabeledXRef {
+    /// The codes found in the local vocabularies, with their IRIs and labels
+    pub fn from_xrefs<'a, I: IntoIterator<Item = (&'a XRef, &'a String)>>(
+        xref: I,
+        vocabularies: &Vocabularies,
+    ) -> Vec<Self> {
+        xref.into_iter()
+            .filter_map(|(x, iri)| match vocabularies.validate(x) {
+                Some(Validation::Known(label) | Validation::Obsolete(label)) => Some(LabeledXRef {
+                    typ: format!("{}:LabeledXRef", MGEND_ONTOLOGY.prefix),
+                    xref: iri.clone(),
+                    xref_label: label.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Write `mgendo:labeled_xref [...]` as the next predicate of `buf`
+    pub fn write_ttl<T: ToTurtle + ?Sized>(
+        items: &[Self],
+        buf: &mut Vec<u8>,
+        owner: &T,
+    ) -> io::Result<()> {
+        for (i, x) in items.iter().enumerate() {
+            if i == 0 {
+                write!(buf, " ;\n  {}:labeled_xref [", MGEND_ONTOLOGY.prefix)?;
+            } else {
+                write!(buf, " , [")?;
             }
-        })
+            write!(buf, "\n    a {}", x.typ)?;
+            write!(buf, " ;\n    {}:seeAlso <{}>", RDFS.prefix, x.xref)?;
+            write!(
+                buf,
+                " ;\n    {}:xref_label {}",
+                MGEND_ONTOLOGY.prefix,
+                owner.string(&x.xref_label)
+            )?;
+            write!(buf, "\n  ]")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_iris_of_xrefs() {
+        let prefix_maps = PrefixMaps::default();
+
+        for (xref, iri) in [
+            (
+                XRef::ICD10("C50.9".to_string()),
+                "http://purl.bioontology.org/ontology/ICD10CM/C50.9",
+            ),
+            (
+                XRef::SnomedCt("254837009".to_string()),
+                "http://identifiers.org/snomedct/254837009",
+            ),
+            (
+                XRef::MeSH("D001943".to_string()),
+                "http://identifiers.org/mesh/D001943",
+            ),
+            (
+                XRef::MedGenUID("146786".to_string()),
+                "http://identifiers.org/medgen/146786",
+            ),
+            (
+                XRef::MedGenCID("C0677776".to_string()),
+                "http://www.ncbi.nlm.nih.gov/medgen/C0677776",
+            ),
+            (
+                XRef::OMIM("604370".to_string()),
+                "https://omim.org/entry/604370",
+            ),
+            (
+                XRef::OMIMPS("PS604370".to_string()),
+                "https://omim.org/phenotypicSeries/PS604370",
+            ),
+            (
+                XRef::HPO("0003002".to_string()),
+                "http://purl.obolibrary.org/obo/HP_0003002",
+            ),
+            (
+                XRef::Orphanet("145".to_string()),
+                "http://purl.obolibrary.org/obo/Orphanet_145",
+            ),
+        ] {
+            assert_eq!(xref.iri(&prefix_maps), iri);
+        }
     }
 }
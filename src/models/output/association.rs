@@ -0,0 +1,228 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::context::Contexts;
+use crate::models::input::Record;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, MED2RDF, MGEND_ASSOCIATION, MGEND_DISEASE, MGEND_ONTOLOGY,
+    MGEND_SUBMISSION, MGEND_VARIANT, PROV, RDFS, SIO,
+};
+use crate::models::output::case::Case;
+use crate::models::output::disease::Disease;
+use crate::models::output::provenance::Provenance;
+use crate::models::output::submission::Submission;
+use crate::models::output::variant::Variant;
+use crate::models::output::{LabeledXRef, Term, XRef};
+use crate::models::vocabulary::Vocabularies;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io;
+use std::io::Write;
+
+/// SIO association
+const SIO_ASSOCIATION: &str = "SIO_000897";
+
+/// Association of a variant with a trait reported by a GWAS, from a row of DataOrigin gwas.
+/// Unlike a `Case`, it carries no case counts.
+#[derive(Debug, Serialize)]
+pub struct Association {
+    id: String,
+    #[serde(rename(serialize = "type"))]
+    typ: Vec<String>,
+    variant: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disease: Option<String>,
+    submission: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allele_origin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disease_area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub_disease_area: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    xref: Vec<String>,
+    #[serde(skip)]
+    xref_code: Vec<XRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    labeled_xref: Vec<LabeledXRef>,
+    case_significance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    was_derived_from: Option<Provenance>,
+}
+
+impl Association {
+    /// Same as the ID of the case the row would otherwise be
+    pub fn id(r: &Record) -> String {
+        Case::id(r)
+    }
+
+    pub fn identifier(&self) -> &String {
+        &self.id
+    }
+
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.was_derived_from.as_ref()
+    }
+
+    /// Same as `Case::set_xref_labels`
+    pub fn set_xref_labels(&mut self, vocabularies: &Vocabularies) {
+        self.labeled_xref =
+            LabeledXRef::from_xrefs(self.xref_code.iter().zip(self.xref.iter()), vocabularies);
+    }
+
+    /// Same as `Case::disambiguate`
+    pub fn disambiguate(&mut self, record: &Record, key: &str, significance: bool) {
+        self.id = Case::disambiguated_id(record, key, significance);
+    }
+}
+
+impl<'a> From<&Record<'a>> for Association {
+    fn from(value: &Record) -> Self {
+        let r = value.row;
+        let xref_code: Vec<XRef> = XRef::from_record(value).into_iter().flatten().collect();
+
+        Association {
+            id: Association::id(value),
+            typ: vec![
+                format!("{}:GWASAssociation", MGEND_ONTOLOGY.prefix),
+                format!("{}:{}", SIO.prefix, SIO_ASSOCIATION),
+            ],
+            variant: format!("{}:{}", MGEND_VARIANT.prefix, Variant::id(value)),
+            disease: Disease::id(value).map(|x| format!("{}:{}", MGEND_DISEASE.prefix, x)),
+            submission: format!("{}:{}", MGEND_SUBMISSION.prefix, Submission::id(value)),
+            allele_origin: r.allele_origin.as_ref().map(|x| x.term()),
+            disease_area: r.disease_area_1.as_ref().map(|x| x.term()),
+            sub_disease_area: r.disease_area_2.as_ref().map(|x| x.term()),
+            xref: xref_code.iter().map(|x| x.iri(value.prefix_maps)).collect(),
+            xref_code,
+            case_significance: r.clinical_significance.term(),
+            labeled_xref: Vec::new(),
+            was_derived_from: value.provenance.clone(),
+        }
+    }
+}
+
+impl NameSpaces for Association {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![
+            MED2RDF,
+            MGEND_ASSOCIATION,
+            MGEND_DISEASE,
+            MGEND_ONTOLOGY,
+            MGEND_SUBMISSION,
+            MGEND_VARIANT,
+            PROV,
+            RDFS,
+            SIO,
+        ]
+    }
+}
+
+impl Contexts for Association {
+    fn contexts() -> Value {
+        json!({
+          "@base": MGEND_ASSOCIATION.prefix ,
+          "id": "@id",
+          "type": "@type",
+          "allele_origin": {
+            "@id": "mgendo:allele_origin",
+            "@type": "@id"
+          },
+          "case_significance": {
+            "@id": "mgendo:case_significance",
+            "@type": "@id"
+          },
+          "disease": {
+            "@id": "m2r:disease",
+            "@type": "@id"
+          },
+          "disease_area": {
+            "@id": "mgendo:disease_area",
+            "@type": "@id"
+          },
+          "sub_disease_area": {
+            "@id": "mgendo:sub_disease_area",
+            "@type": "@id"
+          },
+          "submission": {
+            "@id": "mgendo:submission",
+            "@type": "@id"
+          },
+          "variant": {
+            "@id": "m2r:variation",
+            "@type": "@id"
+          },
+          "xref": {
+            "@id": "rdfs:seeAlso",
+            "@type": "@id"
+          },
+          "labeled_xref": "mgendo:labeled_xref",
+          "xref_label": "mgendo:xref_label",
+          "was_derived_from": "prov:wasDerivedFrom",
+          "source_file": "mgendo:source_file",
+          "line_number": "mgendo:line_number",
+          "row_hash": "mgendo:row_hash",
+        })
+    }
+}
+
+impl ToTurtle for Association {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        write!(
+            vec,
+            "{}:{} a {}",
+            MGEND_ASSOCIATION.prefix,
+            self.pname(&self.id),
+            self.typ.join(", ")
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:variation {}",
+            MED2RDF.prefix,
+            self.pname(&self.variant)
+        )?;
+        if let Some(v) = &self.disease {
+            write!(vec, " ;\n  {}:disease {}", MED2RDF.prefix, self.pname(v))?;
+        }
+        write!(
+            vec,
+            " ;\n  {}:submission {}",
+            MGEND_ONTOLOGY.prefix,
+            self.pname(&self.submission)
+        )?;
+        if let Some(v) = &self.allele_origin {
+            write!(vec, " ;\n  {}:allele_origin {}", MGEND_ONTOLOGY.prefix, v)?;
+        }
+        if let Some(v) = &self.disease_area {
+            write!(vec, " ;\n  {}:disease_area {}", MGEND_ONTOLOGY.prefix, v)?;
+        }
+        if let Some(v) = &self.sub_disease_area {
+            write!(
+                vec,
+                " ;\n  {}:sub_disease_area {}",
+                MGEND_ONTOLOGY.prefix, v
+            )?;
+        }
+        self.write_vec(
+            &mut vec,
+            &self.xref,
+            format!("{}:seeAlso", RDFS.prefix),
+            |x| format!("<{}>", x),
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:case_significance {}",
+            MGEND_ONTOLOGY.prefix, self.case_significance
+        )?;
+
+        LabeledXRef::write_ttl(&self.labeled_xref, &mut vec, self)?;
+        if let Some(x) = &self.was_derived_from {
+            x.write_ttl(&mut vec, self)?;
+        }
+
+        writeln!(vec, " .")?;
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
@@ -1,33 +1,43 @@
 use crate::format::turtle::ToTurtle;
 use crate::models::context::Contexts;
-use crate::models::input::{
-    AlleleOrigin, ClinicalSignificance, CodeType, ConditionIDType, DataOrigin, DiseaseArea1,
-    DiseaseArea2, Record,
-};
+use crate::models::infectious_agent::InfectiousAgents;
+use crate::models::input::{AlleleOrigin, Record, Row};
 use crate::models::name_space::{
     NameSpace, NameSpaces, MED2RDF, MGEND_CASE, MGEND_DISEASE, MGEND_ONTOLOGY, MGEND_SUBMISSION,
-    MGEND_VARIANT, OBO, OLO, RDF, RDFS, SIO,
+    MGEND_VARIANT, OBO, OLO, PROV, RDF, RDFS, SIO,
 };
 use crate::models::output::disease::Disease;
+use crate::models::output::provenance::Provenance;
 use crate::models::output::submission::Submission;
 use crate::models::output::variant::Variant;
-use crate::models::output::XRef;
-use crate::models::regex;
+use crate::models::output::{LabeledXRef, Term, XRef};
+use crate::models::vocabulary::Vocabularies;
 use serde::Serialize;
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io;
 use std::io::Write;
 
-const SO_SOMATIC_VARIANT: &'static str = "SO_0001777";
-const SO_GERMLINE_VARIANT: &'static str = "SO_0001778";
+const SO_SOMATIC_VARIANT: &str = "SO_0001777";
+const SO_GERMLINE_VARIANT: &str = "SO_0001778";
 
-const SEX_LABEL_MALE: &'static str = "male";
-const SEX_LABEL_FEMALE: &'static str = "female";
-const SEX_LABEL_MIXED_GENDER: &'static str = "mixed gender";
-const SEX_LABEL_UNKNOWN: &'static str = "unknown";
-const SEX_LABEL_NOT_PROVIDED: &'static str = "not provided";
+/// Number of hex digits of the content key extending the ID of a case sharing it
+const CASE_KEY_LENGTH: usize = 8;
 
-#[derive(Debug, Serialize)]
+/// Classes of the age ranges of the histogram slots, in the order of the input columns
+const AGE_BINS: [&str; 13] = [
+    "Bin01", "Bin02", "Bin03", "Bin04", "Bin05", "Bin06", "Bin07", "Bin08", "Bin09", "Bin10",
+    "Bin11", "Bin12", "Bin13",
+];
+
+const SEX_LABEL_MALE: &str = "male";
+const SEX_LABEL_FEMALE: &str = "female";
+const SEX_LABEL_MIXED_GENDER: &str = "mixed gender";
+const SEX_LABEL_UNKNOWN: &str = "unknown";
+const SEX_LABEL_NOT_PROVIDED: &str = "not provided";
+
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Histogram {
     #[serde(rename(serialize = "type"))]
     typ: Vec<String>,
@@ -38,7 +48,8 @@ pub struct Histogram {
 }
 
 impl Histogram {
-    pub fn new(values: Vec<i32>, age_type: &str, age_unit: &str) -> Self {
+    /// `values` are the numerator and denominator of each bin
+    pub fn new(values: [(i32, i32); AGE_BINS.len()], age_type: &str, age_unit: &str) -> Self {
         let typ = vec![
             format!("{}:FrequencyDistribution", MED2RDF.prefix),
             format!("{}:OrderedList", OLO.prefix),
@@ -51,12 +62,16 @@ impl Histogram {
             age_unit: age_unit.to_string(),
             slot: values
                 .iter()
+                .zip(AGE_BINS)
                 .enumerate()
-                .map(|(i, &v)| Slot {
+                .map(|(i, (&(n, d), bin))| Slot {
                     index: i + 1,
                     item: Item {
-                        typ: format!("{}:Bin{:0>2}", MGEND_ONTOLOGY.prefix, i + 1),
-                        frequency: v,
+                        typ: format!("{}:{}", MGEND_ONTOLOGY.prefix, bin),
+                        frequency: Some(n),
+                        suppressed: false,
+                        denominator: Some(d),
+                        proportion: proportion(n, d),
                     },
                 })
                 .collect(),
@@ -64,35 +79,240 @@ impl Histogram {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Slot {
     index: usize,
     item: Item,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct Item {
     #[serde(rename(serialize = "type"))]
     typ: String,
-    frequency: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency: Option<i32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    suppressed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    denominator: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proportion: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 pub struct SexCount {
     #[serde(rename(serialize = "type"))]
     typ: String,
     label: String,
-    count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<i32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    suppressed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    denominator: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proportion: Option<String>,
 }
 
 impl SexCount {
-    pub fn new(label: &str, count: i32) -> Self {
+    pub fn new(label: &str, (count, denominator): (i32, i32)) -> Self {
         SexCount {
             typ: format!("{}:SexCount", MGEND_ONTOLOGY.prefix),
             label: label.to_string(),
-            count,
+            count: Some(count),
+            suppressed: false,
+            denominator: Some(denominator),
+            proportion: proportion(count, denominator),
+        }
+    }
+}
+
+/// Numerator divided by denominator as an `xsd:decimal` lexical form, e.g. `0.25`
+fn proportion(numerator: i32, denominator: i32) -> Option<String> {
+    if denominator <= 0 {
+        return None;
+    }
+
+    let s = format!("{:.4}", numerator as f64 / denominator as f64);
+    let s = s.trim_end_matches('0');
+
+    Some(if s.ends_with('.') {
+        format!("{}0", s)
+    } else {
+        s.to_string()
+    })
+}
+
+/// Properties of a case or an association but its provenance, to tell rows of the same content
+/// at different lines
+pub fn content<T: Serialize>(item: &T) -> Value {
+    let mut value = serde_json::to_value(item).unwrap_or_default();
+    if let Value::Object(ref mut x) = value {
+        x.remove("was_derived_from");
+    }
+    value
+}
+
+/// First hex digits of the SHA-256 of the input fields a case or an association is converted from
+/// besides its ID: the data and allele origins, the disease areas, the codes and the counts. The
+/// clinical significance is left out so that a reclassified case keeps its ID, and the codes are
+/// taken as is so that the key does not depend on the prefix map or the suppression of counts.
+pub fn content_key(record: &Record) -> String {
+    let r = record.row;
+
+    let mut fields = vec![
+        r.data_origin.as_ref().map(|x| x.to_string()),
+        r.allele_origin.as_ref().map(|x| x.to_string()),
+        r.disease_area_1.as_ref().map(|x| x.to_string()),
+        r.disease_area_2.as_ref().map(|x| x.to_string()),
+    ]
+    .into_iter()
+    .map(Option::unwrap_or_default)
+    .collect::<Vec<String>>();
+    fields.extend(
+        XRef::from_record(record)
+            .into_iter()
+            .flatten()
+            .map(|x| format!("{}:{}", x.prefix(), x.id())),
+    );
+    fields.extend(
+        sex_counts(r)
+            .iter()
+            .chain(age_counts(r).iter())
+            .chain(age_of_onset_counts(r).iter())
+            .map(|(n, d)| format!("{}/{}", n, d)),
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(fields.join("\t"));
+    format!("{:x}", hasher.finalize())[..CASE_KEY_LENGTH].to_string()
+}
+
+/// Content keys and clinical significances of the rows by the ID of their case or association,
+/// read before the conversion so that an extended ID does not depend on the order of the rows
+#[derive(Debug, Default)]
+pub struct CaseKeys {
+    keys: HashMap<String, BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl CaseKeys {
+    pub fn add(&mut self, id: &str, key: String, significance: String) {
+        self.keys
+            .entry(id.to_string())
+            .or_default()
+            .entry(key)
+            .or_default()
+            .insert(significance);
+    }
+
+    /// `None` if all rows of the ID have the same content, otherwise whether the rows of the key
+    /// differ in their clinical significance only
+    pub fn disambiguation(&self, id: &str, key: &str) -> Option<bool> {
+        let keys = self.keys.get(id)?;
+        let significances = keys.get(key).map_or(0, |x| x.len());
+
+        (keys.len() > 1 || significances > 1).then_some(significances > 1)
+    }
+}
+
+/// Cells to withhold for a minimum cell size of `k`: counts from 1 to k - 1 and, if only one is
+/// withheld, the smallest other count so that it cannot be derived from the denominator.
+///
+/// The distributions of a case share its total, from which the withheld counts of one could be
+/// derived given the others, so `Case::suppress` also withholds the total and the denominators.
+/// Other cases of the submission are not taken into account.
+fn suppression_mask(counts: &[i32], k: i32) -> Vec<bool> {
+    let mut mask: Vec<bool> = counts.iter().map(|&n| n > 0 && n < k).collect();
+
+    if mask.iter().filter(|&&x| x).count() == 1 {
+        if let Some(i) = (0..counts.len())
+            .filter(|&i| !mask[i])
+            .min_by_key(|&i| (counts[i] == 0, counts[i]))
+        {
+            mask[i] = true;
         }
     }
+
+    mask
+}
+
+fn sex_counts(r: &Row) -> [(i32, i32); 5] {
+    [
+        (r.sex_male_numerator, r.sex_male_denominator),
+        (r.sex_female_numerator, r.sex_female_denominator),
+        (r.sex_mixed_gender_numerator, r.sex_mixed_gender_denominator),
+        (r.sex_unknown_numerator, r.sex_unknown_denominator),
+        (r.sex_not_provided_numerator, r.sex_not_provided_denominator),
+    ]
+}
+
+fn age_counts(r: &Row) -> [(i32, i32); AGE_BINS.len()] {
+    [
+        (r.age_0_9_numerator, r.age_0_9_denominator),
+        (r.age_10_19_numerator, r.age_10_19_denominator),
+        (r.age_20_29_numerator, r.age_20_29_denominator),
+        (r.age_30_39_numerator, r.age_30_39_denominator),
+        (r.age_40_49_numerator, r.age_40_49_denominator),
+        (r.age_50_59_numerator, r.age_50_59_denominator),
+        (r.age_60_69_numerator, r.age_60_69_denominator),
+        (r.age_70_79_numerator, r.age_70_79_denominator),
+        (r.age_80_89_numerator, r.age_80_89_denominator),
+        (r.age_90_99_numerator, r.age_90_99_denominator),
+        (r.age_100_numerator, r.age_100_denominator),
+        (r.age_unknown_numerator, r.age_unknown_denominator),
+        (r.age_not_provided_numerator, r.age_not_provided_denominator),
+    ]
+}
+
+fn age_of_onset_counts(r: &Row) -> [(i32, i32); AGE_BINS.len()] {
+    [
+        (r.age_of_onset_0_9_numerator, r.age_of_onset_0_9_denominator),
+        (
+            r.age_of_onset_10_19_numerator,
+            r.age_of_onset_10_19_denominator,
+        ),
+        (
+            r.age_of_onset_20_29_numerator,
+            r.age_of_onset_20_29_denominator,
+        ),
+        (
+            r.age_of_onset_30_39_numerator,
+            r.age_of_onset_30_39_denominator,
+        ),
+        (
+            r.age_of_onset_40_49_numerator,
+            r.age_of_onset_40_49_denominator,
+        ),
+        (
+            r.age_of_onset_50_59_numerator,
+            r.age_of_onset_50_59_denominator,
+        ),
+        (
+            r.age_of_onset_60_69_numerator,
+            r.age_of_onset_60_69_denominator,
+        ),
+        (
+            r.age_of_onset_70_79_numerator,
+            r.age_of_onset_70_79_denominator,
+        ),
+        (
+            r.age_of_onset_80_89_numerator,
+            r.age_of_onset_80_89_denominator,
+        ),
+        (
+            r.age_of_onset_90_99_numerator,
+            r.age_of_onset_90_99_denominator,
+        ),
+        (r.age_of_onset_100_numerator, r.age_of_onset_100_denominator),
+        (
+            r.age_of_onset_unknown_numerator,
+            r.age_of_onset_unknown_denominator,
+        ),
+        (
+            r.age_of_onset_not_provided_numerator,
+            r.age_of_onset_not_provided_denominator,
+        ),
+    ]
 }
 
 #[derive(Debug, Serialize)]
@@ -105,19 +325,28 @@ pub struct Case {
     disease: Option<String>,
     submission: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    variant_type: Option<DataOrigin>,
+    variant_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    allele_origin: Option<AlleleOrigin>,
+    allele_origin: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    disease_area: Option<DiseaseArea1>,
+    disease_area: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    sub_disease_area: Option<DiseaseArea2>,
+    sub_disease_area: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    infectious_agent: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    xref: Vec<String>,
+    #[serde(skip)]
+    xref_code: Vec<XRef>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
-    xref: Vec<XRef>,
-    case_significance: ClinicalSignificance,
-    case_count_total: i32,
+    labeled_xref: Vec<LabeledXRef>,
+    case_significance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    case_count_total: Option<i32>,
     case_age_range_count: Vec<Histogram>,
     case_sex_count: Vec<SexCount>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    was_derived_from: Option<Provenance>,
 }
 
 impl Case {
@@ -131,6 +360,148 @@ impl Case {
     pub fn identifier(&self) -> &String {
         &self.id
     }
+
+    /// Extend the ID with the allele origin and the content key, and the clinical significance if
+    /// `significance`, to tell the case apart from other rows of the submission with the same
+    /// variant and disease
+    pub fn disambiguate(&mut self, record: &Record, key: &str, significance: bool) {
+        self.id = Case::disambiguated_id(record, key, significance);
+    }
+
+    pub fn disambiguated_id(record: &Record, key: &str, significance: bool) -> String {
+        let r = record.row;
+
+        let mut id = format!(
+            "{}_{}_{}",
+            Case::id(record),
+            r.allele_origin
+                .as_ref()
+                .map_or("NA".to_string(), |x| x.to_string()),
+            key
+        );
+        if significance {
+            id.push_str(&format!("_{}", r.clinical_significance));
+        }
+        id
+    }
+
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.was_derived_from.as_ref()
+    }
+
+    /// Codes of the case with their IRIs
+    pub fn xref(&self) -> impl Iterator<Item = (&XRef, &String)> {
+        self.xref_code.iter().zip(self.xref.iter())
+    }
+
+    /// Link the pathogens the row names, e.g. the hepatitis B virus of a hepatitis B case
+    pub fn set_infectious_agents(&mut self, record: &Record, agents: &InfectiousAgents) {
+        self.infectious_agent = agents
+            .find(record.row)
+            .into_iter()
+            .map(|x| format!("{}:NCBITaxon_{}", OBO.prefix, x))
+            .collect();
+    }
+
+    /// Whether the case is of an infectious disease with a known pathogen
+    pub fn has_infectious_agent(&self) -> bool {
+        !self.infectious_agent.is_empty()
+    }
+
+    /// Attach the labels of the codes found in the local vocabularies
+    pub fn set_xref_labels(&mut self, vocabularies: &Vocabularies) {
+        self.labeled_xref = LabeledXRef::from_xrefs(self.xref(), vocabularies);
+    }
+
+    /// Withhold the age and sex counts smaller than `min_cell_size`, and the total, denominators
+    /// and proportions of the case if any count is withheld
+    pub fn suppress(&mut self, min_cell_size: i32) {
+        let mut withheld = false;
+
+        for hist in &mut self.case_age_range_count {
+            let counts: Vec<i32> = hist
+                .slot
+                .iter()
+                .map(|x| x.item.frequency.unwrap_or_default())
+                .collect();
+            for (x, hide) in hist
+                .slot
+                .iter_mut()
+                .zip(suppression_mask(&counts, min_cell_size))
+            {
+                if hide {
+                    x.item.frequency = None;
+                    x.item.proportion = None;
+                    x.item.suppressed = true;
+                    withheld = true;
+                }
+            }
+        }
+
+        let counts: Vec<i32> = self
+            .case_sex_count
+            .iter()
+            .map(|x| x.count.unwrap_or_default())
+            .collect();
+        for (x, hide) in self
+            .case_sex_count
+            .iter_mut()
+            .zip(suppression_mask(&counts, min_cell_size))
+        {
+            if hide {
+                x.count = None;
+                x.proportion = None;
+                x.suppressed = true;
+                withheld = true;
+            }
+        }
+
+        if withheld {
+            self.case_count_total = None;
+            for x in self
+                .case_age_range_count
+                .iter_mut()
+                .flat_map(|x| x.slot.iter_mut())
+            {
+                x.item.denominator = None;
+                x.item.proportion = None;
+            }
+            for x in &mut self.case_sex_count {
+                x.denominator = None;
+                x.proportion = None;
+            }
+        }
+    }
+
+    /// Issues of the numerator/denominator pairs of a row, with the distinct denominators
+    pub fn count_issues(r: &Row) -> Vec<(&'static str, String)> {
+        let pairs: Vec<(i32, i32)> = sex_counts(r)
+            .into_iter()
+            .chain(age_counts(r))
+            .chain([(r.age_other_numerator, r.age_other_denominator)])
+            .chain(age_of_onset_counts(r))
+            .chain([(
+                r.age_of_onset_other_numerator,
+                r.age_of_onset_other_denominator,
+            )])
+            .collect();
+
+        let denominators: BTreeSet<i32> = pairs.iter().map(|x| x.1).collect();
+        let list = denominators
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let mut issues = Vec::new();
+        if denominators.len() > 1 {
+            issues.push(("inconsistent_denominators", list.clone()));
+        }
+        if pairs.iter().any(|(n, d)| n > d) {
+            issues.push(("numerator_exceeds_denominator", list));
+        }
+        issues
+    }
 }
 
 impl<'a> From<&Record<'a>> for Case {
@@ -148,100 +519,27 @@ impl<'a> From<&Record<'a>> for Case {
             _ => {}
         }
 
-        let mut xref = Vec::new();
-        if let (Some(typ), Some(id)) = (&value.row.code_type, &value.row.code_value) {
-            match typ {
-                CodeType::ICD10 => {
-                    if let Some(caps) = regex!(r"[A-Z]\d+(\.[-\d+])?").captures(id) {
-                        xref.push(XRef::ICD10(caps[0].to_string()));
-                    }
-                }
-                CodeType::SnomedCt => {
-                    xref.push(XRef::SnomedCt(id.to_owned()));
-                }
-            }
-        }
-        if let (Some(typ), Some(id)) = (&value.row.condition_id_type, &value.row.condition_id_value)
-        {
-            match typ {
-                ConditionIDType::MeSH => {
-                    if let Some(caps) = regex!(r"[CD]\d+").captures(id) {
-                        xref.push(XRef::MeSH(caps[0].to_string()));
-                    }
-                }
-                ConditionIDType::MedGen => {
-                    if let Some(caps) = regex!(r"^[CN]+\d{4,7}$").captures(id) {
-                        xref.push(XRef::MedGenCID(caps[0].to_string()));
-                    } else if let Some(caps) = regex!(r"^\d{4,7}$").captures(id) {
-                        xref.push(XRef::MedGenUID(caps[0].to_string()));
-                    }
-                }
-                ConditionIDType::OMIM => {
-                    if let Some(caps) = regex!(r"^PS\d+$").captures(id) {
-                        xref.push(XRef::OMIMPS(caps[0].to_string()));
-                    } else if let Some(caps) = regex!(r"^\d+$").captures(id) {
-                        xref.push(XRef::OMIM(caps[0].to_string()));
-                    }
-                }
-                ConditionIDType::HPO => {
-                    if let Some(caps) = regex!(r"\d+").captures(id) {
-                        xref.push(XRef::HPO(caps[0].to_string()));
-                    }
-                }
-                ConditionIDType::Orphanet => {
-                    if let Some(caps) = regex!(r"\d+").captures(id) {
-                        xref.push(XRef::Orphanet(caps[0].to_string()));
-                    }
-                }
-            }
-        }
+        let xref_code: Vec<XRef> = XRef::from_record(value).into_iter().flatten().collect();
 
         let case_age_count = Histogram::new(
-            vec![
-                r.age_0_9_numerator,
-                r.age_10_19_numerator,
-                r.age_20_29_numerator,
-                r.age_30_39_numerator,
-                r.age_40_49_numerator,
-                r.age_50_59_numerator,
-                r.age_60_69_numerator,
-                r.age_70_79_numerator,
-                r.age_80_89_numerator,
-                r.age_90_99_numerator,
-                r.age_100_numerator,
-                r.age_unknown_numerator,
-                r.age_not_provided_numerator,
-            ],
+            age_counts(r),
             "Age",
             format!("{}:SIO_001013", SIO.prefix).as_str(),
         );
 
         let case_age_of_on_set_count = Histogram::new(
-            vec![
-                r.age_of_onset_0_9_numerator,
-                r.age_of_onset_10_19_numerator,
-                r.age_of_onset_20_29_numerator,
-                r.age_of_onset_30_39_numerator,
-                r.age_of_onset_40_49_numerator,
-                r.age_of_onset_50_59_numerator,
-                r.age_of_onset_60_69_numerator,
-                r.age_of_onset_70_79_numerator,
-                r.age_of_onset_80_89_numerator,
-                r.age_of_onset_90_99_numerator,
-                r.age_of_onset_100_numerator,
-                r.age_of_onset_unknown_numerator,
-                r.age_of_onset_not_provided_numerator,
-            ],
+            age_of_onset_counts(r),
             "AgeOfOnset",
             format!("{}:SIO_001013", SIO.prefix).as_str(),
         );
 
+        let [male, female, mixed_gender, unknown, not_provided] = sex_counts(r);
         let case_sex_count = vec![
-            SexCount::new(SEX_LABEL_MALE, r.sex_male_numerator),
-            SexCount::new(SEX_LABEL_FEMALE, r.sex_female_numerator),
-            SexCount::new(SEX_LABEL_MIXED_GENDER, r.sex_mixed_gender_numerator),
-            SexCount::new(SEX_LABEL_UNKNOWN, r.sex_unknown_numerator),
-            SexCount::new(SEX_LABEL_NOT_PROVIDED, r.sex_not_provided_numerator),
+            SexCount::new(SEX_LABEL_MALE, male),
+            SexCount::new(SEX_LABEL_FEMALE, female),
+            SexCount::new(SEX_LABEL_MIXED_GENDER, mixed_gender),
+            SexCount::new(SEX_LABEL_UNKNOWN, unknown),
+            SexCount::new(SEX_LABEL_NOT_PROVIDED, not_provided),
         ];
 
         Case {
@@ -250,15 +548,19 @@ impl<'a> From<&Record<'a>> for Case {
             variant: format!("{}:{}", MGEND_VARIANT.prefix, Variant::id(value)),
             submission: format!("{}:{}", MGEND_SUBMISSION.prefix, Submission::id(value)),
             disease: Disease::id(value).map(|x| format!("{}:{}", MGEND_DISEASE.prefix, x)),
-            variant_type: r.data_origin.clone(),
-            allele_origin: r.allele_origin.clone(),
-            disease_area: r.disease_area_1.clone(),
-            sub_disease_area: r.disease_area_2.clone(),
-            xref,
-            case_significance: r.clinical_significance.clone(),
-            case_count_total: r.age_0_9_denominator,
+            variant_type: r.data_origin.as_ref().map(|x| x.term()),
+            allele_origin: r.allele_origin.as_ref().map(|x| x.term()),
+            disease_area: r.disease_area_1.as_ref().map(|x| x.term()),
+            sub_disease_area: r.disease_area_2.as_ref().map(|x| x.term()),
+            infectious_agent: Vec::new(),
+            xref: xref_code.iter().map(|x| x.iri(value.prefix_maps)).collect(),
+            xref_code,
+            case_significance: r.clinical_significance.term(),
+            case_count_total: Some(r.age_0_9_denominator),
             case_age_range_count: vec![case_age_count, case_age_of_on_set_count],
             case_sex_count,
+            labeled_xref: Vec::new(),
+            was_derived_from: value.provenance.clone(),
         }
     }
 }
@@ -273,6 +575,7 @@ impl NameSpaces for Case {
             MGEND_SUBMISSION,
             MGEND_VARIANT,
             OBO,
+            PROV,
             OLO,
             RDF,
             RDFS,
@@ -287,7 +590,10 @@ impl Contexts for Case {
           "@base": MGEND_CASE.prefix ,
           "id": "@id",
           "type": "@type",
-          "allele_origin": "mgendo:allele_origin",
+          "allele_origin": {
+            "@id": "mgendo:allele_origin",
+            "@type": "@id"
+          },
           "case_age_range_count": "mgendo:case_age_range_count",
           "age_type": "mgendo:age_type",
           "age_unit": {
@@ -296,8 +602,12 @@ impl Contexts for Case {
           },
           "case_count_total": "mgendo:case_count_total",
           "case_sex_count": "mgendo:case_sex_count",
-          "case_significance": "mgendo:case_significance",
+          "case_significance": {
+            "@id": "mgendo:case_significance",
+            "@type": "@id"
+          },
           "count": "rdf:value",
+          "denominator": "mgendo:denominator",
           "disease": {
             "@id": "m2r:disease",
             "@type": "@id"
@@ -306,14 +616,31 @@ impl Contexts for Case {
             "@id": "rdfs:seeAlso",
             "@type": "@id"
           },
-          "disease_area": "mgendo:disease_area",
+          "labeled_xref": "mgendo:labeled_xref",
+          "xref_label": "mgendo:xref_label",
+          "disease_area": {
+            "@id": "mgendo:disease_area",
+            "@type": "@id"
+          },
           "frequency": "mgendo:frequency",
           "index": "olo:index",
+          "infectious_agent": {
+            "@id": "mgendo:infectious_agent",
+            "@type": "@id"
+          },
           "item": "olo:item",
           "label": "rdfs:label",
           "length": "olo:length",
+          "proportion": {
+            "@id": "mgendo:proportion",
+            "@type": "xsd:decimal"
+          },
           "slot": "olo:slot",
-          "sub_disease_area": "mgendo:sub_disease_area",
+          "suppressed": "mgendo:suppressed",
+          "sub_disease_area": {
+            "@id": "mgendo:sub_disease_area",
+            "@type": "@id"
+          },
           "submission": {
             "@id": "mgendo:submission",
             "@type": "@id"
@@ -322,7 +649,14 @@ impl Contexts for Case {
             "@id": "m2r:variation",
             "@type": "@id"
           },
-          "variant_type": "mgendo:variant_type",
+          "variant_type": {
+            "@id": "mgendo:variant_type",
+            "@type": "@id"
+          },
+          "was_derived_from": "prov:wasDerivedFrom",
+          "source_file": "mgendo:source_file",
+          "line_number": "mgendo:line_number",
+          "row_hash": "mgendo:row_hash",
         })
     }
 }
@@ -359,30 +693,30 @@ impl ToTurtle for Case {
             )?;
         }
         if let Some(v) = &self.variant_type {
-            if let Ok(v) = serde_json::to_string(v) {
-                write!(vec, " ;\n  {}:variant_type {}", MGEND_ONTOLOGY.prefix, v)?;
-            }
+            write!(vec, " ;\n  {}:variant_type {}", MGEND_ONTOLOGY.prefix, v)?;
         }
         if let Some(v) = &self.allele_origin {
-            if let Ok(v) = serde_json::to_string(v) {
-                write!(vec, " ;\n  {}:allele_origin {}", MGEND_ONTOLOGY.prefix, v)?;
-            }
+            write!(vec, " ;\n  {}:allele_origin {}", MGEND_ONTOLOGY.prefix, v)?;
         }
         if let Some(v) = &self.disease_area {
-            if let Ok(v) = serde_json::to_string(v) {
-                write!(vec, " ;\n  {}:disease_area {}", MGEND_ONTOLOGY.prefix, v)?;
-            }
+            write!(vec, " ;\n  {}:disease_area {}", MGEND_ONTOLOGY.prefix, v)?;
         }
         if let Some(v) = &self.sub_disease_area {
-            if let Ok(v) = serde_json::to_string(v) {
-                write!(
-                    vec,
-                    " ;\n  {}:sub_disease_area {}",
-                    MGEND_ONTOLOGY.prefix, v
-                )?;
-            }
+            write!(
+                vec,
+                " ;\n  {}:sub_disease_area {}",
+                MGEND_ONTOLOGY.prefix, v
+            )?;
         }
-        if self.xref.len() > 0 {
+        if !self.infectious_agent.is_empty() {
+            write!(
+                vec,
+                " ;\n  {}:infectious_agent {}",
+                MGEND_ONTOLOGY.prefix,
+                self.infectious_agent.join(", ")
+            )?;
+        }
+        if !self.xref.is_empty() {
             write!(
                 vec,
                 " ;\n  {}:seeAlso {}",
@@ -394,18 +728,18 @@ impl ToTurtle for Case {
                     .join(", ")
             )?;
         }
-        if let Ok(v) = serde_json::to_string(&self.case_significance) {
+        write!(
+            vec,
+            " ;\n  {}:case_significance {}",
+            MGEND_ONTOLOGY.prefix, self.case_significance
+        )?;
+        if let Some(v) = self.case_count_total {
             write!(
                 vec,
-                " ;\n  {}:case_significance {}",
+                " ;\n  {}:case_count_total {}",
                 MGEND_ONTOLOGY.prefix, v
             )?;
         }
-        write!(
-            vec,
-            " ;\n  {}:case_count_total {}",
-            MGEND_ONTOLOGY.prefix, self.case_count_total
-        )?;
 
         {
             for (i, hist) in self.case_age_range_count.iter().enumerate() {
@@ -441,7 +775,26 @@ impl ToTurtle for Case {
                     write!(vec, "\n      {}:index {}", OLO.prefix, x.index)?;
                     write!(vec, " ;\n      {}:item [", OLO.prefix)?;
                     write!(vec, "\n        a {}", x.item.typ)?;
-                    write!(vec, " ;\n        {}:value {}", RDF.prefix, x.item.frequency)?;
+                    if let Some(v) = x.item.frequency {
+                        write!(vec, " ;\n        {}:value {}", RDF.prefix, v)?;
+                    }
+                    if x.item.suppressed {
+                        write!(vec, " ;\n        {}:suppressed true", MGEND_ONTOLOGY.prefix)?;
+                    }
+                    if let Some(v) = x.item.denominator {
+                        write!(
+                            vec,
+                            " ;\n        {}:denominator {}",
+                            MGEND_ONTOLOGY.prefix, v
+                        )?;
+                    }
+                    if let Some(v) = &x.item.proportion {
+                        write!(
+                            vec,
+                            " ;\n        {}:proportion {}",
+                            MGEND_ONTOLOGY.prefix, v
+                        )?;
+                    }
                     write!(vec, "\n      ]")?;
                     write!(vec, "\n    ]")?;
                 }
@@ -464,12 +817,28 @@ impl ToTurtle for Case {
                     RDFS.prefix,
                     self.string(&x.label)
                 )?;
-                write!(vec, " ;\n    {}:value {}", RDF.prefix, x.count)?;
+                if let Some(v) = x.count {
+                    write!(vec, " ;\n    {}:value {}", RDF.prefix, v)?;
+                }
+                if x.suppressed {
+                    write!(vec, " ;\n    {}:suppressed true", MGEND_ONTOLOGY.prefix)?;
+                }
+                if let Some(v) = x.denominator {
+                    write!(vec, " ;\n    {}:denominator {}", MGEND_ONTOLOGY.prefix, v)?;
+                }
+                if let Some(v) = &x.proportion {
+                    write!(vec, " ;\n    {}:proportion {}", MGEND_ONTOLOGY.prefix, v)?;
+                }
 
                 write!(vec, "\n  ]")?;
             }
         }
 
+        LabeledXRef::write_ttl(&self.labeled_xref, &mut vec, self)?;
+        if let Some(x) = &self.was_derived_from {
+            x.write_ttl(&mut vec, self)?;
+        }
+
         writeln!(vec, " .")?;
 
         String::from_utf8(vec)
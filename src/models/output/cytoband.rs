@@ -0,0 +1,129 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::context::Contexts;
+use crate::models::cytoband::Definition;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, FALDO, HCO, MGEND_CYTOBAND, MGEND_ONTOLOGY, RDFS,
+};
+use crate::models::output::variant::{ExactPosition, Region};
+use crate::Assembly;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io;
+use std::io::Write;
+
+#[derive(Debug, Serialize)]
+pub struct CytoBand {
+    id: String,
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    label: String,
+    stain: String,
+    location: Region,
+}
+
+impl CytoBand {
+    pub fn id<S: AsRef<str>, T: AsRef<str>>(assembly: &Assembly, chromosome: S, name: T) -> String {
+        format!("{}_{}{}", assembly, chromosome.as_ref(), name.as_ref())
+    }
+
+    pub fn label<S: AsRef<str>, T: AsRef<str>>(chromosome: S, name: T) -> String {
+        format!("{}{}", chromosome.as_ref(), name.as_ref())
+    }
+
+    pub fn new<S: AsRef<str>>(assembly: &Assembly, chromosome: S, band: &Definition) -> Self {
+        let reference = format!("{}:{}/{}", HCO.prefix, chromosome.as_ref(), assembly);
+
+        CytoBand {
+            id: CytoBand::id(assembly, &chromosome, &band.name),
+            typ: format!("{}:CytoBand", MGEND_ONTOLOGY.prefix),
+            label: CytoBand::label(&chromosome, &band.name),
+            stain: band.gie_stain.clone(),
+            location: Region {
+                begin: ExactPosition {
+                    position: band.chrom_start + 1,
+                    reference: reference.clone(),
+                },
+                end: ExactPosition {
+                    position: band.chrom_end,
+                    reference,
+                },
+            },
+        }
+    }
+}
+
+impl NameSpaces for CytoBand {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![FALDO, HCO, MGEND_CYTOBAND, MGEND_ONTOLOGY, RDFS]
+    }
+}
+
+impl Contexts for CytoBand {
+    fn contexts() -> Value {
+        json!({
+          "@base": MGEND_CYTOBAND.prefix ,
+          "id": "@id",
+          "type": "@type",
+          "ExactPosition": {
+            "@id": "faldo:ExactPosition",
+            "@type": "@id"
+          },
+          "Region": {
+            "@id": "faldo:Region",
+            "@type": "@id"
+          },
+          "begin": "faldo:begin",
+          "end": "faldo:end",
+          "label": "rdfs:label",
+          "location": "faldo:location",
+          "position": "faldo:position",
+          "reference": {
+            "@id": "faldo:reference",
+            "@type": "@id"
+          },
+          "stain": "mgendo:stain",
+        })
+    }
+}
+
+impl ToTurtle for CytoBand {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        write!(
+            vec,
+            "{}:{} a {}",
+            MGEND_CYTOBAND.prefix,
+            self.pname(&self.id),
+            self.typ
+        )?;
+        write!(vec, " ;\n  rdfs:label {}", self.string(&self.label))?;
+        write!(
+            vec,
+            " ;\n  {}:stain {}",
+            MGEND_ONTOLOGY.prefix,
+            self.string(&self.stain)
+        )?;
+
+        write!(vec, " ;\n  {}:location [", FALDO.prefix)?;
+        write!(vec, "\n    a {}:Region", FALDO.prefix)?;
+        for (pred, p) in [("begin", &self.location.begin), ("end", &self.location.end)] {
+            write!(vec, " ;\n    {}:{} [", FALDO.prefix, pred)?;
+            write!(vec, "\n      a {}:ExactPosition", FALDO.prefix)?;
+            write!(vec, " ;\n      {}:position {}", FALDO.prefix, p.position)?;
+            write!(
+                vec,
+                " ;\n      {}:reference {}",
+                FALDO.prefix,
+                self.pname(&p.reference)
+            )?;
+            write!(vec, "\n    ]")?;
+        }
+        write!(vec, "\n  ]")?;
+
+        writeln!(vec, " .")?;
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
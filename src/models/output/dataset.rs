@@ -0,0 +1,353 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, DCAT, DCTERMS, FOAF, HCO, MGEND, MGEND_ONTOLOGY, PAV, PROV, RDFS, SPDX,
+    VOID, XSD,
+};
+use crate::{reader, Assembly};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Description of the converted dataset, written to mgend_dataset.ttl
+#[derive(Debug)]
+pub struct Dataset {
+    id: String,
+    typ: Vec<String>,
+    config: DatasetConfig,
+    assembly: String,
+    source: Source,
+    converter: String,
+    version: String,
+    created_on: String,
+    subset: Vec<Subset>,
+    suppression: Option<Suppression>,
+}
+
+/// Title, description, license and publisher of the dataset
+#[derive(Debug, Default)]
+pub struct DatasetConfig {
+    title: Option<String>,
+    description: Option<String>,
+    license: Option<String>,
+    publisher: Option<String>,
+}
+
+/// Input file the dataset was converted from
+#[derive(Debug)]
+pub struct Source {
+    name: String,
+    sha256: String,
+}
+
+/// Output file of an entity, described as a subset of the dataset
+#[derive(Debug)]
+pub struct Subset {
+    id: String,
+    title: String,
+    entities: usize,
+    /// Number of triples, known for Turtle only
+    triples: Option<usize>,
+    data_dump: String,
+}
+
+/// Small-cell suppression policy applied to the age and sex counts and the totals of the cases
+#[derive(Debug)]
+pub struct Suppression {
+    typ: String,
+    min_cell_size: i32,
+    comment: String,
+}
+
+impl DatasetConfig {
+    /// Read a TSV of keys (`title`, `description`, `license`, `publisher`) and values, e.g.
+    /// `license\thttps://creativecommons.org/licenses/by/4.0/`
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut config = DatasetConfig::default();
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| err(&e))?;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('\t')
+                .ok_or_else(|| err(&format!("expected a key and a value: {}", line)))?;
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "title" => config.title = value,
+                "description" => config.description = value,
+                "license" => config.license = value,
+                "publisher" => config.publisher = value,
+                x => Err(err(&format!("unknown key: {}", x)))?,
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl Source {
+    /// Take the file name and the SHA-256 of the file as is, compressed or not
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut hasher = Sha256::new();
+        io::copy(&mut File::open(path.as_ref())?, &mut hasher)?;
+
+        Ok(Source {
+            name: path
+                .as_ref()
+                .file_name()
+                .map_or(String::new(), |x| x.to_string_lossy().to_string()),
+            sha256: format!("{:x}", hasher.finalize()),
+        })
+    }
+}
+
+impl Dataset {
+    pub fn new(
+        config: DatasetConfig,
+        source: Source,
+        assembly: &Assembly,
+        min_cell_size: Option<i32>,
+    ) -> Self {
+        Dataset {
+            id: "dataset".to_string(),
+            typ: vec![
+                format!("{}:Dataset", VOID.prefix),
+                format!("{}:Dataset", DCAT.prefix),
+            ],
+            config,
+            assembly: format!("{}:{}", HCO.prefix, assembly),
+            source,
+            converter: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            created_on: timestamp(SystemTime::now()),
+            subset: Vec::new(),
+            suppression: min_cell_size.map(|k| Suppression {
+                typ: format!("{}:SmallCellSuppression", MGEND_ONTOLOGY.prefix),
+                min_cell_size: k,
+                comment: format!(
+                    "Counts from 1 to {} are withheld, as well as the smallest other count of a distribution with a single withheld count. The total, denominators and proportions of a case with any withheld count are withheld too, as its distributions share the total.",
+                    k - 1
+                ),
+            }),
+        }
+    }
+
+    /// Describe an output file of `entities` resources of the entity named `title`, e.g. `case`,
+    /// and `triples` triples if counted. The file name is written as an IRI relative to
+    /// mgend_dataset.ttl.
+    pub fn add_subset(
+        &mut self,
+        title: &str,
+        data_dump: &str,
+        entities: usize,
+        triples: Option<usize>,
+    ) {
+        self.subset.push(Subset {
+            id: format!("{}_{}", self.id, title),
+            title: title.to_string(),
+            entities,
+            triples,
+            data_dump: data_dump.to_string(),
+        });
+    }
+}
+
+/// `xsd:dateTime` lexical form of the time in UTC, e.g. `2024-04-01T09:30:00Z`
+fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs() as i64);
+    let (days, secs) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+
+    // civil date of the days since 1970-01-01 in the proleptic Gregorian calendar
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+impl NameSpaces for Dataset {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![
+            DCAT,
+            DCTERMS,
+            FOAF,
+            HCO,
+            MGEND,
+            MGEND_ONTOLOGY,
+            PAV,
+            PROV,
+            RDFS,
+            SPDX,
+            VOID,
+            XSD,
+        ]
+    }
+}
+
+impl ToTurtle for Dataset {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        // IRI if the value looks like one, otherwise a literal
+        let value = |x: &String| {
+            if x.starts_with("http://") || x.starts_with("https://") {
+                format!("<{}>", x)
+            } else {
+                self.string(x)
+            }
+        };
+
+        write!(
+            vec,
+            "{}:{} a {}",
+            MGEND.prefix,
+            self.id,
+            self.typ.join(", ")
+        )?;
+        for (pred, x) in [
+            ("title", &self.config.title),
+            ("description", &self.config.description),
+            ("license", &self.config.license),
+        ] {
+            if let Some(x) = x {
+                write!(vec, " ;\n  {}:{} {}", DCTERMS.prefix, pred, value(x))?;
+            }
+        }
+        // a publisher given by name is an agent of that name
+        match &self.config.publisher {
+            Some(x) if x.starts_with("http://") || x.starts_with("https://") => {
+                write!(vec, " ;\n  {}:publisher <{}>", DCTERMS.prefix, x)?;
+            }
+            Some(x) => {
+                write!(vec, " ;\n  {}:publisher [", DCTERMS.prefix)?;
+                write!(vec, "\n    a {}:Agent", FOAF.prefix)?;
+                write!(vec, " ;\n    {}:name {}", FOAF.prefix, self.string(x))?;
+                write!(vec, "\n  ]")?;
+            }
+            None => {}
+        }
+        write!(
+            vec,
+            " ;\n  {}:assembly {}",
+            MGEND_ONTOLOGY.prefix, self.assembly
+        )?;
+
+        write!(vec, " ;\n  {}:source [", DCTERMS.prefix)?;
+        write!(vec, "\n    a {}:Distribution", DCAT.prefix)?;
+        write!(
+            vec,
+            " ;\n    {}:title {}",
+            DCTERMS.prefix,
+            self.string(&self.source.name)
+        )?;
+        write!(vec, " ;\n    {}:checksum [", SPDX.prefix)?;
+        write!(vec, "\n      a {}:Checksum", SPDX.prefix)?;
+        write!(
+            vec,
+            " ;\n      {}:algorithm {}:checksumAlgorithm_sha256",
+            SPDX.prefix, SPDX.prefix
+        )?;
+        write!(
+            vec,
+            " ;\n      {}:checksumValue {}",
+            SPDX.prefix,
+            self.string(&self.source.sha256)
+        )?;
+        write!(vec, "\n    ]")?;
+        write!(vec, "\n  ]")?;
+
+        write!(vec, " ;\n  {}:createdWith [", PAV.prefix)?;
+        write!(vec, "\n    a {}:SoftwareAgent", PROV.prefix)?;
+        write!(
+            vec,
+            " ;\n    {}:label {}",
+            RDFS.prefix,
+            self.string(&self.converter)
+        )?;
+        write!(
+            vec,
+            " ;\n    {}:version {}",
+            PAV.prefix,
+            self.string(&self.version)
+        )?;
+        write!(vec, "\n  ]")?;
+        write!(
+            vec,
+            " ;\n  {}:createdOn {}^^{}:dateTime",
+            PAV.prefix,
+            self.string(&self.created_on),
+            XSD.prefix
+        )?;
+
+        if let Some(triples) = self.subset.iter().map(|x| x.triples).sum::<Option<usize>>() {
+            write!(vec, " ;\n  {}:triples {}", VOID.prefix, triples)?;
+        }
+        self.write_vec(
+            &mut vec,
+            &self.subset,
+            format!("{}:subset", VOID.prefix),
+            |x| format!("{}:{}", MGEND.prefix, x.id),
+        )?;
+
+        if let Some(x) = &self.suppression {
+            write!(vec, " ;\n  {}:suppression [", MGEND_ONTOLOGY.prefix)?;
+            write!(vec, "\n    a {}", x.typ)?;
+            write!(
+                vec,
+                " ;\n    {}:min_cell_size {}",
+                MGEND_ONTOLOGY.prefix, x.min_cell_size
+            )?;
+            write!(
+                vec,
+                " ;\n    {}:comment {}",
+                RDFS.prefix,
+                self.string(&x.comment)
+            )?;
+            write!(vec, "\n  ]")?;
+        }
+        writeln!(vec, " .")?;
+
+        for x in &self.subset {
+            write!(vec, "\n{}:{} a {}:Dataset", MGEND.prefix, x.id, VOID.prefix)?;
+            write!(
+                vec,
+                " ;\n  {}:title {}",
+                DCTERMS.prefix,
+                self.string(&x.title)
+            )?;
+            write!(vec, " ;\n  {}:entities {}", VOID.prefix, x.entities)?;
+            if let Some(triples) = x.triples {
+                write!(vec, " ;\n  {}:triples {}", VOID.prefix, triples)?;
+            }
+            write!(vec, " ;\n  {}:dataDump <{}>", VOID.prefix, x.data_dump)?;
+            writeln!(vec, " .")?;
+        }
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
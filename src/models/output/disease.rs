@@ -2,49 +2,266 @@ use crate::format::turtle::ToTurtle;
 use crate::models::context::Contexts;
 use crate::models::input::Record;
 use crate::models::name_space::{
-    NameSpace, NameSpaces, MED2RDF, MGEND_CASE, MGEND_DISEASE, MGEND_ONTOLOGY, RDF, RDFS,
+    NameSpace, NameSpaces, MED2RDF, MGEND_ASSOCIATION, MGEND_CASE, MGEND_DISEASE, MGEND_ONTOLOGY,
+    RDF, RDFS, SKOS,
 };
+use crate::models::ontology::{MatchType, Ontologies};
+use crate::models::output::association::Association;
 use crate::models::output::case::Case;
+use crate::models::output::provenance::{LineRanges, Provenance};
+use crate::models::output::XRef;
+use crate::models::vocabulary::{Validation, Vocabularies};
+use crate::Normalization;
 use serde::Serialize;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::io;
 use std::io::Write;
 
-#[derive(Debug, Serialize)]
+/// Number of hex digits of the SHA-256 of the normalized name used as disease ID
+const DISEASE_ID_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Disease {
     pub id: String,
     #[serde(rename(serialize = "type"))]
     typ: String,
     label: String,
+    /// Other spellings of the name normalized to the same ID
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alt_label: Vec<String>,
     case: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    association: Vec<String>,
+    #[serde(skip_serializing_if = "LineRanges::is_empty")]
+    source_lines: LineRanges,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    xref: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    xref_count: Vec<XRefCount>,
+    /// Whether the rows of the disease give different codes of the same system
+    xref_conflict: bool,
+    /// Distinct sets of codes of each system given by a row
+    #[serde(skip)]
+    xref_sets: BTreeMap<&'static str, BTreeSet<Vec<String>>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exact_match: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    close_match: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    broad_match: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    narrow_match: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ontology_match: Vec<OntologyMatch>,
+}
+
+/// IDs of the normalized disease names of an input file, lengthened past `DISEASE_ID_LENGTH`
+/// for the names whose IDs would otherwise collide
+#[derive(Debug, Default)]
+pub struct DiseaseIds {
+    lengthened: HashMap<String, String>,
+}
+
+/// A term of a local ontology matching the name of a disease
+#[derive(Debug, Clone, Serialize)]
+pub struct OntologyMatch {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    term: String,
+    match_type: MatchType,
+}
+
+/// Number of cases of a disease giving a cross-reference
+#[derive(Debug, Clone, Serialize)]
+pub struct XRefCount {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    xref: String,
+    /// Label of the code in the local vocabulary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xref_label: Option<String>,
+    count: usize,
+    #[serde(skip)]
+    code: XRef,
 }
 
 impl Disease {
     pub fn id(record: &Record) -> Option<String> {
-        match record.row.disease_name {
-            None => None,
-            Some(ref name) => {
-                let mut hasher = Sha256::new();
-                hasher.update(name);
-
-                Some(format!("{:x}", hasher.finalize()))
-            }
-        }
+        record.disease_id.clone()
     }
 
     pub fn identifier(&self) -> &String {
         &self.id
     }
 
+    pub fn label(&self) -> &String {
+        &self.label
+    }
+
+    pub fn alt_label(&self) -> &[String] {
+        &self.alt_label
+    }
+
+    /// Record another spelling of the name
+    pub fn add_label<S: AsRef<str>>(&mut self, label: S) {
+        let label = label.as_ref();
+        if label != self.label && !self.alt_label.iter().any(|x| x == label) {
+            self.alt_label.push(label.to_string());
+        }
+    }
+
+    /// Same as `Variant::add_source_line`
+    pub fn add_source_line(&mut self, provenance: Option<&Provenance>) {
+        self.source_lines.add(provenance);
+    }
+
     pub fn add_case(&mut self, case: &Case) {
+        self.source_lines.add(case.provenance());
         self.case
-            .push(format!("{}:{}", MGEND_CASE.prefix, case.identifier()))
+            .push(format!("{}:{}", MGEND_CASE.prefix, case.identifier()));
+
+        let mut systems: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+        for (x, iri) in case.xref() {
+            systems.entry(x.system()).or_default().push(iri.clone());
+            match self.xref_count.iter_mut().find(|c| c.xref == *iri) {
+                Some(c) => c.count += 1,
+                None => {
+                    self.xref.push(iri.clone());
+                    self.xref_count.push(XRefCount {
+                        typ: format!("{}:XRefCount", MGEND_ONTOLOGY.prefix),
+                        xref: iri.clone(),
+                        xref_label: None,
+                        count: 1,
+                        code: x.clone(),
+                    });
+                }
+            }
+        }
+        for (system, mut codes) in systems {
+            codes.sort();
+            codes.dedup();
+            self.xref_sets.entry(system).or_default().insert(codes);
+        }
+        self.xref_conflict = self.xref_sets.values().any(|x| x.len() > 1);
+    }
+
+    pub fn add_association(&mut self, association: &Association) {
+        self.source_lines.add(association.provenance());
+        self.association.push(format!(
+            "{}:{}",
+            MGEND_ASSOCIATION.prefix,
+            association.identifier()
+        ));
+    }
+
+    /// Map the name and its other spellings to ontology terms. Returns false if nothing matches.
+    pub fn set_mapping(
+        &mut self,
+        ontologies: &Ontologies,
+        normalization: &[Normalization],
+    ) -> bool {
+        let names: Vec<&String> = std::iter::once(&self.label)
+            .chain(self.alt_label.iter())
+            .collect();
+
+        for (term, match_type) in ontologies.find(&names, normalization) {
+            // a name broader than the term makes the term a narrower match, and vice versa
+            match match_type {
+                x if x.is_exact() => &mut self.exact_match,
+                MatchType::BroadSynonym => &mut self.narrow_match,
+                MatchType::NarrowSynonym => &mut self.broad_match,
+                _ => &mut self.close_match,
+            }
+            .push(term.iri.clone());
+            self.ontology_match.push(OntologyMatch {
+                typ: format!("{}:OntologyMatch", MGEND_ONTOLOGY.prefix),
+                term: term.iri.clone(),
+                match_type,
+            });
+        }
+
+        !self.ontology_match.is_empty()
+    }
+
+    /// Attach the labels of the codes found in the local vocabularies
+    pub fn set_xref_labels(&mut self, vocabularies: &Vocabularies) {
+        for x in self.xref_count.iter_mut() {
+            x.xref_label = match vocabularies.validate(&x.code) {
+                Some(Validation::Known(label) | Validation::Obsolete(label)) => Some(label.clone()),
+                _ => None,
+            };
+        }
+    }
+
+    /// Code systems for which the rows of the disease disagree, with all their codes. Several
+    /// codes given together by each row, e.g. for a compound condition, are no conflict.
+    pub fn conflicts(&self) -> BTreeMap<&'static str, Vec<&String>> {
+        self.xref_sets
+            .iter()
+            .filter(|(_, x)| x.len() > 1)
+            .map(|(system, x)| {
+                let mut codes: Vec<&String> = x.iter().flatten().collect();
+                codes.sort();
+                codes.dedup();
+                (*system, codes)
+            })
+            .collect()
     }
 }
 
-const ERR_BLANK_DISEASE_ID: &'static str = "disease id is blank";
-const ERR_BLANK_DISEASE_NAME: &'static str = "disease name is blank";
+fn hash(name: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(name);
+
+    format!("{:x}", hasher.finalize())
+}
+
+impl DiseaseIds {
+    /// Lengthen the IDs of all names sharing an ID until they differ, so that the IDs do not
+    /// depend on the order of the rows
+    pub fn new<I: IntoIterator<Item = String>>(names: I) -> Self {
+        let mut groups: HashMap<String, BTreeMap<String, String>> = HashMap::new();
+        for name in names {
+            let hash = hash(&name);
+            groups
+                .entry(hash[..DISEASE_ID_LENGTH].to_string())
+                .or_default()
+                .insert(name, hash);
+        }
+
+        let mut lengthened = HashMap::new();
+        for group in groups.into_values().filter(|x| x.len() > 1) {
+            let len = (DISEASE_ID_LENGTH..64)
+                .find(|&n| {
+                    let ids: BTreeSet<&str> = group.values().map(|x| &x[..n]).collect();
+                    ids.len() == group.len()
+                })
+                .unwrap_or(64);
+            for (name, hash) in group {
+                lengthened.insert(name, hash[..len].to_string());
+            }
+        }
+
+        DiseaseIds { lengthened }
+    }
+
+    pub fn id(&self, name: &str) -> String {
+        match self.lengthened.get(name) {
+            Some(x) => x.clone(),
+            None => hash(name)[..DISEASE_ID_LENGTH].to_string(),
+        }
+    }
+
+    /// Names whose ID was lengthened, with the ID
+    pub fn lengthened(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.lengthened.iter()
+    }
+}
+
+const ERR_BLANK_DISEASE_ID: &str = "disease id is blank";
+const ERR_BLANK_DISEASE_NAME: &str = "disease name is blank";
 
 impl<'a> TryFrom<&Record<'a>> for Disease {
     type Error = &'static str;
@@ -56,7 +273,19 @@ impl<'a> TryFrom<&Record<'a>> for Disease {
             id: Disease::id(record).ok_or(ERR_BLANK_DISEASE_ID)?,
             typ: format!("{}:Disease", MED2RDF.prefix),
             label: r.disease_name.clone().ok_or(ERR_BLANK_DISEASE_NAME)?,
+            alt_label: Vec::new(),
             case: Vec::new(),
+            association: Vec::new(),
+            source_lines: LineRanges::default(),
+            xref: Vec::new(),
+            xref_count: Vec::new(),
+            xref_conflict: false,
+            xref_sets: BTreeMap::new(),
+            exact_match: Vec::new(),
+            close_match: Vec::new(),
+            broad_match: Vec::new(),
+            narrow_match: Vec::new(),
+            ontology_match: Vec::new(),
         })
     }
 }
@@ -70,6 +299,7 @@ impl NameSpaces for Disease {
             MGEND_DISEASE,
             RDF,
             RDFS,
+            SKOS,
         ]
     }
 }
@@ -80,19 +310,47 @@ impl Contexts for Disease {
           "@base": MGEND_DISEASE.prefix ,
           "id": "@id",
           "type": "@type",
+          "alt_label": "skos:altLabel",
+          "association": {
+            "@id": "mgendo:association",
+            "@type": "@id"
+          },
+          "broad_match": {
+            "@id": "skos:broadMatch",
+            "@type": "@id"
+          },
           "case": {
             "@id": "mgendo:case",
             "@type": "@id"
           },
-          "disease": {
-            "@id": "mgendo:disease",
+          "close_match": {
+            "@id": "skos:closeMatch",
             "@type": "@id"
           },
-          "gene": {
-            "@id": "mgendo:gene",
+          "count": "rdf:value",
+          "exact_match": {
+            "@id": "skos:exactMatch",
             "@type": "@id"
           },
           "label": "rdfs:label",
+          "match_type": "mgendo:match_type",
+          "narrow_match": {
+            "@id": "skos:narrowMatch",
+            "@type": "@id"
+          },
+          "ontology_match": "mgendo:ontology_match",
+          "term": {
+            "@id": "mgendo:term",
+            "@type": "@id"
+          },
+          "xref": {
+            "@id": "rdfs:seeAlso",
+            "@type": "@id"
+          },
+          "xref_conflict": "mgendo:xref_conflict",
+          "xref_label": "mgendo:xref_label",
+          "xref_count": "mgendo:xref_count",
+          "source_lines": "mgendo:source_lines",
         })
     }
 }
@@ -103,6 +361,12 @@ impl ToTurtle for Disease {
 
         write!(vec, "{}:{} a {}", MGEND_DISEASE.prefix, self.id, self.typ)?;
         write!(vec, " ;\n  rdfs:label {}", self.string(&self.label))?;
+        self.write_vec(
+            &mut vec,
+            &self.alt_label,
+            format!("{}:altLabel", SKOS.prefix),
+            |x| self.string(x),
+        )?;
 
         self.write_vec(
             &mut vec,
@@ -110,6 +374,77 @@ impl ToTurtle for Disease {
             format!("{}:case", MGEND_ONTOLOGY.prefix),
             |x| self.pname(x),
         )?;
+        self.write_vec(
+            &mut vec,
+            &self.association,
+            format!("{}:association", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        self.write_vec(
+            &mut vec,
+            &self.xref,
+            format!("{}:seeAlso", RDFS.prefix),
+            |x| format!("<{}>", x),
+        )?;
+        for (i, x) in self.xref_count.iter().enumerate() {
+            if i == 0 {
+                write!(vec, " ;\n  {}:xref_count [", MGEND_ONTOLOGY.prefix)?;
+            } else {
+                write!(vec, " , [")?;
+            }
+            write!(vec, "\n    a {}", x.typ)?;
+            write!(vec, " ;\n    {}:seeAlso <{}>", RDFS.prefix, x.xref)?;
+            if let Some(ref v) = x.xref_label {
+                write!(
+                    vec,
+                    " ;\n    {}:xref_label {}",
+                    MGEND_ONTOLOGY.prefix,
+                    self.string(v)
+                )?;
+            }
+            write!(vec, " ;\n    {}:value {}", RDF.prefix, x.count)?;
+            write!(vec, "\n  ]")?;
+        }
+        write!(
+            vec,
+            " ;\n  {}:xref_conflict {}",
+            MGEND_ONTOLOGY.prefix, self.xref_conflict
+        )?;
+        for (pred, v) in [
+            ("exactMatch", &self.exact_match),
+            ("closeMatch", &self.close_match),
+            ("broadMatch", &self.broad_match),
+            ("narrowMatch", &self.narrow_match),
+        ] {
+            self.write_vec(&mut vec, v, format!("{}:{}", SKOS.prefix, pred), |x| {
+                format!("<{}>", x)
+            })?;
+        }
+        for (i, x) in self.ontology_match.iter().enumerate() {
+            if i == 0 {
+                write!(vec, " ;\n  {}:ontology_match [", MGEND_ONTOLOGY.prefix)?;
+            } else {
+                write!(vec, " , [")?;
+            }
+            write!(vec, "\n    a {}", x.typ)?;
+            write!(vec, " ;\n    {}:term <{}>", MGEND_ONTOLOGY.prefix, x.term)?;
+            write!(
+                vec,
+                " ;\n    {}:match_type {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(x.match_type.to_string())
+            )?;
+            write!(vec, "\n  ]")?;
+        }
+
+        if !self.source_lines.is_empty() {
+            write!(
+                vec,
+                " ;\n  {}:source_lines {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(self.source_lines.to_string())
+            )?;
+        }
 
         writeln!(vec, " .")?;
 
@@ -1,14 +1,20 @@
 use crate::format::turtle::ToTurtle;
 use crate::models::context::Contexts;
-use crate::models::hgnc::HGNC;
+use crate::models::hgnc::{Definition, HGNC};
 use crate::models::input::Record;
-use crate::models::name_space::{NameSpace, NameSpaces, HGNC, MED2RDF, MGEND_GENE, RDF, RDFS};
-use once_cell::sync::OnceCell;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, DCTERMS, ENSEMBL, HGNC, MED2RDF, MGEND_DISEASE, MGEND_GENE,
+    MGEND_ONTOLOGY, MGEND_VARIANT, NCBIGENE, OMIM, RDF, RDFS, SKOS, UNIPROT,
+};
+use crate::models::output::disease::Disease;
+use crate::models::output::variant::Variant;
+use crate::models::output::Term;
 use serde::Serialize;
 use serde_json::{json, Value};
+use std::collections::HashSet;
 use std::io;
 use std::io::Write;
-use std::path::Path;
+use strum::Display;
 
 #[derive(Debug, Serialize)]
 pub struct Gene {
@@ -16,8 +22,62 @@ pub struct Gene {
     #[serde(rename(serialize = "type"))]
     typ: String,
     label: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alt_label: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pref_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locus_group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cytogenetic_location: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     xref: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ncbigene: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ensembl: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    omim: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    uniprot: Vec<String>,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    variant: HashSet<String>,
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    disease: HashSet<String>,
+    variant_count: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    significance_count: Vec<SignificanceCount>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    disease_area: Vec<String>,
+}
+
+/// Number of cases of a gene, or of submissions of an aggregate interpretation, with a clinical
+/// significance
+#[derive(Debug, Serialize)]
+pub struct SignificanceCount {
+    #[serde(rename(serialize = "type"))]
+    pub typ: String,
+    pub case_significance: String,
+    pub count: i32,
+}
+
+impl SignificanceCount {
+    /// Count another case or submission of the clinical significance
+    pub fn add(counts: &mut Vec<SignificanceCount>, significance: String) {
+        match counts
+            .iter_mut()
+            .find(|x| x.case_significance == significance)
+        {
+            Some(x) => x.count += 1,
+            None => counts.push(SignificanceCount {
+                typ: format!("{}:SignificanceCount", MGEND_ONTOLOGY.prefix),
+                case_significance: significance,
+                count: 1,
+            }),
+        }
+    }
 }
 
 impl Gene {
@@ -29,24 +89,147 @@ impl Gene {
             .map(|x| x.split(",").map(|x| x.trim()).collect())
     }
 
-    pub fn hgnc<P: AsRef<Path>>(hgnc_path: P) -> &'static HGNC {
-        static CELL: OnceCell<HGNC> = OnceCell::new();
-        CELL.get_or_init(|| HGNC::from_path(hgnc_path.as_ref()).unwrap())
+    /// `HGNC_<HGNC ID>` if the symbol resolves to a single HGNC entry, otherwise the symbol itself
+    pub fn id<T: AsRef<str>>(symbol: T, hgnc: &HGNC) -> String {
+        match hgnc.resolve(&symbol).id() {
+            Some(id) => format!("HGNC_{}", id),
+            None => symbol.as_ref().to_string(),
+        }
     }
 
-    pub fn new<T: AsRef<str>, P: AsRef<Path>>(symbol: T, hgnc: P) -> Self {
+    pub fn new<T: AsRef<str>>(symbol: T, hgnc: &HGNC) -> Self {
+        let resolution = hgnc.resolve(&symbol);
+        let definition = resolution.id().and_then(|x| hgnc.entry(x));
+
+        // pipe-separated identifiers of a cross-reference column
+        let ids = |ns: &NameSpace, f: fn(&Definition) -> &Option<String>| {
+            definition
+                .and_then(|x| f(x).as_ref())
+                .iter()
+                .flat_map(|x| x.split('|'))
+                .map(|x| x.trim())
+                .filter(|x| !x.is_empty())
+                .map(|x| format!("{}:{}", ns.prefix, x))
+                .collect::<Vec<String>>()
+        };
+
         Gene {
-            id: symbol.as_ref().to_string(),
+            id: Gene::id(&symbol, hgnc),
             typ: format!("{}:Gene", MED2RDF.prefix),
-            label: symbol.as_ref().to_string(),
-            xref: Gene::hgnc(hgnc).find(symbol).map(|x| x.to_owned()),
+            label: definition.map_or(symbol.as_ref().to_string(), |x| x.symbol.clone()),
+            alt_label: Vec::new(),
+            pref_label: definition.and_then(|x| x.name.clone()),
+            description: definition.and_then(|x| x.name.clone()),
+            locus_group: definition.and_then(|x| x.locus_group.clone()),
+            cytogenetic_location: definition.and_then(|x| x.location.clone()),
+            xref: resolution.id().map(|x| x.to_owned()),
+            ncbigene: ids(&NCBIGENE, |x| &x.entrez_id),
+            ensembl: ids(&ENSEMBL, |x| &x.ensembl_gene_id),
+            omim: ids(&OMIM, |x| &x.omim_id),
+            uniprot: ids(&UNIPROT, |x| &x.uniprot_ids),
+            variant: HashSet::new(),
+            disease: HashSet::new(),
+            variant_count: 0,
+            significance_count: Vec::new(),
+            disease_area: Vec::new(),
+        }
+    }
+
+    /// Link the variant and disease of a case submitted for the gene and update the summaries
+    pub fn add_case(&mut self, record: &Record) {
+        let r = record.row;
+
+        self.add_association(record);
+
+        SignificanceCount::add(&mut self.significance_count, r.clinical_significance.term());
+
+        if let Some(area) = r.disease_area_1.as_ref().map(|x| x.term()) {
+            if !self.disease_area.contains(&area) {
+                self.disease_area.push(area);
+            }
+        }
+    }
+
+    /// Link the variant and disease of a GWAS association without counting it as a case
+    pub fn add_association(&mut self, record: &Record) {
+        self.variant
+            .insert(format!("{}:{}", MGEND_VARIANT.prefix, Variant::id(record)));
+        self.variant_count = self.variant.len();
+
+        if let Some(id) = Disease::id(record) {
+            self.disease
+                .insert(format!("{}:{}", MGEND_DISEASE.prefix, id));
+        }
+    }
+
+    /// Record another submitted symbol of the same gene, e.g. a previous or alias symbol
+    pub fn add_symbol<T: AsRef<str>>(&mut self, symbol: T) {
+        let symbol = symbol.as_ref();
+        if symbol != self.label && !self.alt_label.iter().any(|x| x == symbol) {
+            self.alt_label.push(symbol.to_string());
+        }
+    }
+}
+
+/// Agreement between the submitted genes of a variant and the genes it overlaps in the annotation
+#[derive(Debug, Clone, PartialEq, Display, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum GeneConcordance {
+    /// Every submitted gene overlaps the variant
+    Concordant,
+    /// Some submitted gene does not overlap the variant
+    Discordant,
+    /// No gene submitted, but the variant overlaps some
+    NotSubmitted,
+    /// Genes submitted, but the variant overlaps none
+    NoOverlap,
+}
+
+impl GeneConcordance {
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(
+        submitted: &[S],
+        computed: &[T],
+        hgnc: &HGNC,
+    ) -> Option<Self> {
+        let same = |a: &str, b: &str| a == b || Gene::id(a, hgnc) == Gene::id(b, hgnc);
+
+        match (submitted.is_empty(), computed.is_empty()) {
+            (true, true) => None,
+            (true, false) => Some(GeneConcordance::NotSubmitted),
+            (false, true) => Some(GeneConcordance::NoOverlap),
+            (false, false) => Some(
+                if submitted
+                    .iter()
+                    .all(|a| computed.iter().any(|b| same(a.as_ref(), b.as_ref())))
+                {
+                    GeneConcordance::Concordant
+                } else {
+                    GeneConcordance::Discordant
+                },
+            ),
         }
     }
 }
 
 impl NameSpaces for Gene {
     fn namespaces() -> Vec<NameSpace> {
-        vec![MED2RDF, MGEND_GENE, RDF, RDFS, HGNC]
+        vec![
+            MED2RDF,
+            MGEND_DISEASE,
+            MGEND_GENE,
+            MGEND_ONTOLOGY,
+            MGEND_VARIANT,
+            RDF,
+            RDFS,
+            SKOS,
+            DCTERMS,
+            HGNC,
+            NCBIGENE,
+            ENSEMBL,
+            OMIM,
+            UNIPROT,
+        ]
     }
 }
 
@@ -56,7 +239,47 @@ impl Contexts for Gene {
           "@base": MGEND_GENE.prefix ,
           "id": "@id",
           "type": "@type",
+          "alt_label": "skos:altLabel",
+          "case_significance": {
+            "@id": "mgendo:case_significance",
+            "@type": "@id"
+          },
+          "count": "rdf:value",
+          "cytogenetic_location": "mgendo:cytogenetic_location",
+          "description": "dcterms:description",
+          "disease": {
+            "@id": "m2r:disease",
+            "@type": "@id"
+          },
+          "disease_area": {
+            "@id": "mgendo:disease_area",
+            "@type": "@id"
+          },
+          "ensembl": {
+            "@id": "mgendo:ensembl",
+            "@type": "@id"
+          },
           "label": "rdfs:label",
+          "locus_group": "mgendo:locus_group",
+          "ncbigene": {
+            "@id": "mgendo:ncbigene",
+            "@type": "@id"
+          },
+          "omim": {
+            "@id": "mgendo:omim",
+            "@type": "@id"
+          },
+          "pref_label": "skos:prefLabel",
+          "significance_count": "mgendo:significance_count",
+          "uniprot": {
+            "@id": "mgendo:uniprot",
+            "@type": "@id"
+          },
+          "variant": {
+            "@id": "mgendo:variant",
+            "@type": "@id"
+          },
+          "variant_count": "mgendo:variant_count",
           "xref": {
             "@id": "rdfs:seeAlso",
             "@type": "@id"
@@ -69,11 +292,102 @@ impl ToTurtle for Gene {
     fn to_ttl(&self) -> io::Result<String> {
         let mut vec = Vec::new();
 
-        write!(vec, "{}:{} a {}", MGEND_GENE.prefix, self.id, self.typ)?;
+        write!(
+            vec,
+            "{}:{} a {}",
+            MGEND_GENE.prefix,
+            self.pname(&self.id),
+            self.typ
+        )?;
         write!(vec, " ;\n  rdfs:label {}", self.string(&self.label))?;
+        self.write_vec(
+            &mut vec,
+            &self.alt_label,
+            format!("{}:altLabel", SKOS.prefix),
+            |x| self.string(x),
+        )?;
+        if let Some(ref v) = self.pref_label {
+            write!(vec, " ;\n  {}:prefLabel {}", SKOS.prefix, self.string(v))?;
+        }
+        if let Some(ref v) = self.description {
+            write!(
+                vec,
+                " ;\n  {}:description {}",
+                DCTERMS.prefix,
+                self.string(v)
+            )?;
+        }
+        if let Some(ref v) = self.locus_group {
+            write!(
+                vec,
+                " ;\n  {}:locus_group {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(v)
+            )?;
+        }
+        if let Some(ref v) = self.cytogenetic_location {
+            write!(
+                vec,
+                " ;\n  {}:cytogenetic_location {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(v)
+            )?;
+        }
         if let Some(ref v) = self.xref {
             write!(vec, " ;\n  rdfs:seeAlso {}:{}", HGNC.prefix, v)?;
         };
+        for (pred, v) in [
+            ("ncbigene", &self.ncbigene),
+            ("ensembl", &self.ensembl),
+            ("omim", &self.omim),
+            ("uniprot", &self.uniprot),
+        ] {
+            self.write_vec(
+                &mut vec,
+                v,
+                format!("{}:{}", MGEND_ONTOLOGY.prefix, pred),
+                |x| self.pname(x),
+            )?;
+        }
+
+        self.write_set(
+            &mut vec,
+            &self.variant,
+            format!("{}:variant", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        self.write_set(
+            &mut vec,
+            &self.disease,
+            format!("{}:disease", MED2RDF.prefix),
+            |x| self.pname(x),
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:variant_count {}",
+            MGEND_ONTOLOGY.prefix, self.variant_count
+        )?;
+        for (i, x) in self.significance_count.iter().enumerate() {
+            if i == 0 {
+                write!(vec, " ;\n  {}:significance_count [", MGEND_ONTOLOGY.prefix)?;
+            } else {
+                write!(vec, " , [")?;
+            }
+            write!(vec, "\n    a {}", x.typ)?;
+            write!(
+                vec,
+                " ;\n    {}:case_significance {}",
+                MGEND_ONTOLOGY.prefix, x.case_significance
+            )?;
+            write!(vec, " ;\n    {}:value {}", RDF.prefix, x.count)?;
+            write!(vec, "\n  ]")?;
+        }
+        self.write_vec(
+            &mut vec,
+            &self.disease_area,
+            format!("{}:disease_area", MGEND_ONTOLOGY.prefix),
+            |x| x.to_string(),
+        )?;
 
         writeln!(vec, " .")?;
 
@@ -0,0 +1,254 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::context::Contexts;
+use crate::models::input::Record;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, MGEND_ONTOLOGY, MGEND_ORGANIZATION, MGEND_PERSON, MGEND_SUBMISSION, ORG,
+    OWL, RDFS, SKOS,
+};
+use crate::models::output::normalize;
+use crate::models::output::submission::Submission;
+use crate::models::regex;
+use crate::{reader, Normalization};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+/// Number of hex digits of the SHA-256 of the normalized name used as organization or person ID
+pub const NAME_ID_LENGTH: usize = 16;
+
+const ROR: &str = "https://ror.org/";
+
+/// Normalization of the names of organizations and persons before minting IDs
+pub const NAME_NORMALIZATION: [Normalization; 3] = [
+    Normalization::NFKC,
+    Normalization::Whitespace,
+    Normalization::Case,
+];
+
+/// ROR IDs of institute names
+#[derive(Debug, Default)]
+pub struct RorMapping {
+    ids: HashMap<String, String>,
+    /// Lines without a name and a valid ROR ID
+    invalid: Vec<String>,
+}
+
+impl RorMapping {
+    /// Read a TSV of institute names and ROR IDs or IRIs, e.g. `Tokyo University\t057zh3y96`
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut ids = HashMap::new();
+        let mut invalid = Vec::new();
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| err(&e))?;
+            if line.starts_with('#') || line.trim().is_empty() {
+                continue;
+            }
+            match line.split_once('\t').and_then(|(name, id)| {
+                regex!(r"^(?:https?://ror\.org/)?(0[a-z0-9]{8})$")
+                    .captures(id.trim())
+                    .map(|caps| (name, caps[1].to_string()))
+            }) {
+                Some((name, id)) => {
+                    ids.insert(normalize(name, &NAME_NORMALIZATION), id);
+                }
+                None => invalid.push(line),
+            }
+        }
+
+        Ok(RorMapping { ids, invalid })
+    }
+
+    fn get(&self, name: &str) -> Option<&String> {
+        self.ids.get(&normalize(name, &NAME_NORMALIZATION))
+    }
+
+    /// Lines of the TSV without a name and a valid ROR ID, which are not used
+    pub fn invalid(&self) -> &[String] {
+        &self.invalid
+    }
+}
+
+/// First hex digits of the SHA-256 of the normalized parts
+pub fn name_id(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for (i, x) in parts.iter().enumerate() {
+        if i > 0 {
+            hasher.update("\t");
+        }
+        hasher.update(normalize(x, &NAME_NORMALIZATION));
+    }
+
+    format!("{:x}", hasher.finalize())[..NAME_ID_LENGTH].to_string()
+}
+
+#[derive(Debug, Serialize)]
+pub struct Organization {
+    id: String,
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    label: String,
+    /// Other spellings of the name normalized to the same ID
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alt_label: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    same_as: Option<String>,
+    member: HashSet<String>,
+    submission: HashSet<String>,
+    submission_count: usize,
+    case_count: usize,
+    #[serde(skip)]
+    case: HashSet<String>,
+}
+
+impl Organization {
+    /// ROR ID of the institute if mapped, otherwise the first hex digits of the SHA-256 of its
+    /// normalized name
+    pub fn id<S: AsRef<str>>(name: S, ror: &RorMapping) -> String {
+        match ror.get(name.as_ref()) {
+            Some(x) => x.to_string(),
+            None => name_id(&[name.as_ref()]),
+        }
+    }
+
+    pub fn new<S: AsRef<str>>(name: S, ror: &RorMapping) -> Self {
+        let name = name.as_ref();
+
+        Organization {
+            id: Organization::id(name, ror),
+            typ: format!("{}:Organization", ORG.prefix),
+            label: name.to_string(),
+            alt_label: Vec::new(),
+            same_as: ror.get(name).map(|x| format!("{}{}", ROR, x)),
+            member: HashSet::new(),
+            submission: HashSet::new(),
+            submission_count: 0,
+            case_count: 0,
+            case: HashSet::new(),
+        }
+    }
+
+    /// Link a submitter of the institute and count the case of ID `case` they submitted, once
+    /// however many rows give it. GWAS associations are not counted as cases.
+    pub fn add_case<S: AsRef<str>>(&mut self, name: S, person: &str, record: &Record, case: &str) {
+        let name = name.as_ref();
+        if name != self.label && !self.alt_label.iter().any(|x| x == name) {
+            self.alt_label.push(name.to_string());
+        }
+
+        self.member
+            .insert(format!("{}:{}", MGEND_PERSON.prefix, person));
+        self.submission.insert(format!(
+            "{}:{}",
+            MGEND_SUBMISSION.prefix,
+            Submission::id(record)
+        ));
+        self.submission_count = self.submission.len();
+        if !record.is_gwas() {
+            self.case.insert(case.to_string());
+            self.case_count = self.case.len();
+        }
+    }
+}
+
+impl NameSpaces for Organization {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![
+            MGEND_ONTOLOGY,
+            MGEND_ORGANIZATION,
+            MGEND_PERSON,
+            MGEND_SUBMISSION,
+            ORG,
+            OWL,
+            RDFS,
+            SKOS,
+        ]
+    }
+}
+
+impl Contexts for Organization {
+    fn contexts() -> Value {
+        json!({
+          "@base": MGEND_ORGANIZATION.prefix ,
+          "id": "@id",
+          "type": "@type",
+          "alt_label": "skos:altLabel",
+          "case_count": "mgendo:case_count",
+          "label": "rdfs:label",
+          "member": {
+            "@id": "org:hasMember",
+            "@type": "@id"
+          },
+          "same_as": {
+            "@id": "owl:sameAs",
+            "@type": "@id"
+          },
+          "submission": {
+            "@id": "mgendo:submission",
+            "@type": "@id"
+          },
+          "submission_count": "mgendo:submission_count",
+        })
+    }
+}
+
+impl ToTurtle for Organization {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        write!(
+            vec,
+            "{}:{} a {}",
+            MGEND_ORGANIZATION.prefix, self.id, self.typ
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:label {}",
+            RDFS.prefix,
+            self.string(&self.label)
+        )?;
+        self.write_vec(
+            &mut vec,
+            &self.alt_label,
+            format!("{}:altLabel", SKOS.prefix),
+            |x| self.string(x),
+        )?;
+        if let Some(v) = &self.same_as {
+            write!(vec, " ;\n  {}:sameAs <{}>", OWL.prefix, v)?;
+        }
+        self.write_set(
+            &mut vec,
+            &self.member,
+            format!("{}:hasMember", ORG.prefix),
+            |x| x.to_string(),
+        )?;
+        self.write_set(
+            &mut vec,
+            &self.submission,
+            format!("{}:submission", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:submission_count {}",
+            MGEND_ONTOLOGY.prefix, self.submission_count
+        )?;
+        write!(
+            vec,
+            " ;\n  {}:case_count {}",
+            MGEND_ONTOLOGY.prefix, self.case_count
+        )?;
+
+        writeln!(vec, " .")?;
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::context::Contexts;
+use crate::models::input::Record;
+use crate::models::name_space::{
+    NameSpace, NameSpaces, FOAF, MGEND_ONTOLOGY, MGEND_ORGANIZATION, MGEND_PERSON,
+    MGEND_SUBMISSION, ORG,
+};
+use crate::models::output::organization::{name_id, Organization, RorMapping};
+use crate::models::output::submission::Submission;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io;
+use std::io::Write;
+
+/// Submitter of a name at an organization
+#[derive(Debug, Serialize)]
+pub struct Person {
+    id: String,
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    name: String,
+    member_of: String,
+    submission: HashSet<String>,
+}
+
+impl Person {
+    /// First hex digits of the SHA-256 of the organization ID and the normalized name, as the
+    /// same name at different institutes is not taken for the same person
+    pub fn id<S: AsRef<str>, T: AsRef<str>>(name: S, institute: T, ror: &RorMapping) -> String {
+        name_id(&[&Organization::id(institute, ror), name.as_ref()])
+    }
+
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(name: S, institute: T, ror: &RorMapping) -> Self {
+        Person {
+            id: Person::id(&name, &institute, ror),
+            typ: format!("{}:Person", FOAF.prefix),
+            name: name.as_ref().to_string(),
+            member_of: format!(
+                "{}:{}",
+                MGEND_ORGANIZATION.prefix,
+                Organization::id(institute, ror)
+            ),
+            submission: HashSet::new(),
+        }
+    }
+
+    pub fn identifier(&self) -> &String {
+        &self.id
+    }
+
+    pub fn add_submission(&mut self, record: &Record) {
+        self.submission.insert(format!(
+            "{}:{}",
+            MGEND_SUBMISSION.prefix,
+            Submission::id(record)
+        ));
+    }
+}
+
+impl NameSpaces for Person {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![
+            FOAF,
+            MGEND_ONTOLOGY,
+            MGEND_ORGANIZATION,
+            MGEND_PERSON,
+            MGEND_SUBMISSION,
+            ORG,
+        ]
+    }
+}
+
+impl Contexts for Person {
+    fn contexts() -> Value {
+        json!({
+          "@base": MGEND_PERSON.prefix ,
+          "id": "@id",
+          "type": "@type",
+          "member_of": {
+            "@id": "org:memberOf",
+            "@type": "@id"
+          },
+          "name": "foaf:name",
+          "submission": {
+            "@id": "mgendo:submission",
+            "@type": "@id"
+          },
+        })
+    }
+}
+
+impl ToTurtle for Person {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        write!(vec, "{}:{} a {}", MGEND_PERSON.prefix, self.id, self.typ)?;
+        write!(
+            vec,
+            " ;\n  {}:name {}",
+            FOAF.prefix,
+            self.string(&self.name)
+        )?;
+        write!(vec, " ;\n  {}:memberOf {}", ORG.prefix, self.member_of)?;
+        self.write_set(
+            &mut vec,
+            &self.submission,
+            format!("{}:submission", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+
+        writeln!(vec, " .")?;
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
@@ -0,0 +1,125 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::name_space::{MGEND_ONTOLOGY, PROV};
+use csv::StringRecord;
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Write;
+
+/// Row of the input file a case or association was built from
+#[derive(Debug, Clone, Serialize)]
+pub struct Provenance {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    source_file: String,
+    line_number: u64,
+    /// SHA-256 of the fields of the row joined by tabs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_hash: Option<String>,
+}
+
+impl Provenance {
+    /// Without `hash` where counts are suppressed: the hash of the full row would let the few
+    /// possible values of a withheld count be tried against it
+    pub fn new(source_file: &str, row: &StringRecord, hash: bool) -> Self {
+        let row_hash = hash.then(|| {
+            let mut hasher = Sha256::new();
+            for (i, x) in row.iter().enumerate() {
+                if i > 0 {
+                    hasher.update("\t");
+                }
+                hasher.update(x);
+            }
+            format!("{:x}", hasher.finalize())
+        });
+
+        Provenance {
+            typ: format!("{}:Entity", PROV.prefix),
+            source_file: source_file.to_string(),
+            line_number: row.position().map_or(0, |x| x.line()),
+            row_hash,
+        }
+    }
+
+    /// Write `prov:wasDerivedFrom [...]` as the next predicate of `buf`
+    pub fn write_ttl<T: ToTurtle + ?Sized>(&self, buf: &mut Vec<u8>, owner: &T) -> io::Result<()> {
+        write!(buf, " ;\n  {}:wasDerivedFrom [", PROV.prefix)?;
+        write!(buf, "\n    a {}", self.typ)?;
+        write!(
+            buf,
+            " ;\n    {}:source_file {}",
+            MGEND_ONTOLOGY.prefix,
+            owner.string(&self.source_file)
+        )?;
+        write!(
+            buf,
+            " ;\n    {}:line_number {}",
+            MGEND_ONTOLOGY.prefix, self.line_number
+        )?;
+        if let Some(x) = &self.row_hash {
+            write!(
+                buf,
+                " ;\n    {}:row_hash {}",
+                MGEND_ONTOLOGY.prefix,
+                owner.string(x)
+            )?;
+        }
+        write!(buf, "\n  ]")?;
+
+        Ok(())
+    }
+}
+
+/// Line numbers of the rows an aggregated entity was built from, written as ranges, e.g. `2-4,7`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineRanges {
+    lines: Vec<u64>,
+}
+
+impl LineRanges {
+    pub fn add(&mut self, provenance: Option<&Provenance>) {
+        if let Some(x) = provenance {
+            if let Err(i) = self.lines.binary_search(&x.line_number) {
+                self.lines.insert(i, x.line_number);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+impl Display for LineRanges {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut i = 0;
+        while i < self.lines.len() {
+            let mut j = i;
+            while j + 1 < self.lines.len() && self.lines[j + 1] == self.lines[j] + 1 {
+                j += 1;
+            }
+
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            if i == j {
+                write!(f, "{}", self.lines[i])?;
+            } else {
+                write!(f, "{}-{}", self.lines[i], self.lines[j])?;
+            }
+            i = j + 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for LineRanges {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
@@ -1,25 +1,47 @@
 use crate::format::turtle::ToTurtle;
 use crate::models::context::Contexts;
-use crate::models::input::{DiseaseArea1, Record};
+use crate::models::input::{Record, Row};
 use crate::models::name_space::{
-    NameSpace, NameSpaces, FOAF, MGEND_CASE, MGEND_ONTOLOGY, MGEND_SUBMISSION, OLO, ORG, PAV, RDFS,
+    NameSpace, NameSpaces, MGEND_ASSOCIATION, MGEND_CASE, MGEND_ONTOLOGY, MGEND_PERSON,
+    MGEND_SUBMISSION, OLO, PAV, RDFS,
 };
+use crate::models::output::association::Association;
 use crate::models::output::case::Case;
+use crate::models::output::organization::name_id;
+use crate::models::output::person::Person;
+use crate::models::output::provenance::LineRanges;
+use crate::models::output::Term;
 use serde::Serialize;
 use serde_json::{json, Value};
 use std::io;
 use std::io::Write;
 
+/// SubmissionID of the rows without one
+const NOT_PROVIDED: &str = "not provided";
+
+/// Whether the SubmissionID is blank or `not provided` in any case
+fn is_not_provided(submission_id: &str) -> bool {
+    let x = submission_id.trim();
+    x.is_empty() || x.eq_ignore_ascii_case(NOT_PROVIDED)
+}
+
 #[derive(Debug, Serialize)]
 pub struct Submission {
     id: String,
     #[serde(rename(serialize = "type"))]
-    typ: String,
+    typ: Vec<String>,
     label: String,
     submissions: Submissions,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    disease_area: Option<DiseaseArea1>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    disease_area: Vec<String>,
     case: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    association: Vec<String>,
+    #[serde(skip_serializing_if = "LineRanges::is_empty")]
+    source_lines: LineRanges,
+    /// Distinct submitters of the rows, e.g. `Taro Yamada (Kyoto University); ...`
+    #[serde(skip)]
+    submitter_lists: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -33,79 +55,154 @@ pub struct Submissions {
 #[derive(Debug, Serialize)]
 pub struct Slot {
     index: usize,
-    item: Submitter,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct Submitter {
-    #[serde(rename(serialize = "type"))]
-    typ: String,
-    name: String,
-    organization: Organization,
-}
-
-#[derive(Debug, Clone, Serialize)]
-pub struct Organization {
-    #[serde(rename(serialize = "type"))]
-    typ: String,
-    label: String,
+    /// Person IRI
+    item: String,
 }
 
 impl Submission {
+    /// SubmissionID, or for the rows without one `not_provided` followed by the first hex digits
+    /// of the SHA-256 of the IDs of their submitters, so that the rows of different submitters
+    /// are not taken for one submission
     pub fn id(r: &Record) -> String {
-        r.row.submission_id.clone()
+        if !is_not_provided(&r.row.submission_id) {
+            return r.row.submission_id.trim().to_string();
+        }
+
+        let mut persons: Vec<String> = submitters(r.row)
+            .into_iter()
+            .map(|(name, inst)| Person::id(name, inst, r.ror))
+            .collect();
+        persons.sort();
+        persons.dedup();
+
+        let bucket = NOT_PROVIDED.replace(' ', "_");
+        if persons.is_empty() {
+            bucket
+        } else {
+            let parts: Vec<&str> = persons.iter().map(|x| x.as_str()).collect();
+            format!("{}_{}", bucket, name_id(&parts))
+        }
+    }
+
+    pub fn identifier(&self) -> &String {
+        &self.id
+    }
+
+    /// Whether this is the bucket of the rows without SubmissionID rather than an actual submission
+    pub fn is_not_provided(&self) -> bool {
+        self.label == NOT_PROVIDED
     }
 
     pub fn add_case(&mut self, case: &Case) {
         self.case
             .push(format!("{}:{}", MGEND_CASE.prefix, case.identifier()))
     }
+
+    pub fn add_association(&mut self, association: &Association) {
+        self.association.push(format!(
+            "{}:{}",
+            MGEND_ASSOCIATION.prefix,
+            association.identifier()
+        ));
+    }
+
+    /// Add the line, the disease area and the submitters of another row of the submission
+    pub fn add_record(&mut self, record: &Record) {
+        let r = record.row;
+        self.source_lines.add(record.provenance.as_ref());
+
+        if let Some(area) = r.disease_area_1.as_ref().map(|x| x.term()) {
+            if !self.disease_area.contains(&area) {
+                self.disease_area.push(area);
+            }
+        }
+
+        let submitters = submitters(r);
+        for (name, inst) in &submitters {
+            let item = format!(
+                "{}:{}",
+                MGEND_PERSON.prefix,
+                Person::id(name, inst, record.ror)
+            );
+            if !self.submissions.submitters.iter().any(|x| x.item == item) {
+                self.submissions.submitters.push(Slot {
+                    index: self.submissions.submitters.len() + 1,
+                    item,
+                });
+            }
+        }
+        self.submissions.length = self.submissions.submitters.len();
+
+        // the same submitters in another order are not a conflict
+        let mut list: Vec<String> = submitters
+            .iter()
+            .map(|(name, inst)| format!("{} ({})", name, inst))
+            .collect();
+        list.sort();
+        list.dedup();
+        let list = list.join("; ");
+        if !self.submitter_lists.contains(&list) {
+            self.submitter_lists.push(list);
+        }
+    }
+
+    /// Fields whose values differ between the rows of the submission, with the distinct values
+    pub fn conflicts(&self) -> Vec<(&'static str, String)> {
+        let mut conflicts = Vec::new();
+
+        if self.disease_area.len() > 1 {
+            conflicts.push(("disease_area", self.disease_area.join(",")));
+        }
+        if self.submitter_lists.len() > 1 {
+            conflicts.push(("submitters", self.submitter_lists.join(" | ")));
+        }
+
+        conflicts
+    }
 }
 
-fn add_submitter(submitters: &mut Vec<Submitter>, name: &String, inst: &String) {
-    submitters.push(Submitter {
-        typ: format!("{}:Person", FOAF.prefix),
-        name: name.to_string(),
-        organization: Organization {
-            typ: format!("{}:Organization", ORG.prefix),
-            label: inst.to_string(),
-        },
+/// Names and institutes of the submitters of a row
+pub fn submitters(r: &Row) -> Vec<(&String, &String)> {
+    [
+        (&r.submitter_name_1, &r.submitter_institute_1),
+        (&r.submitter_name_2, &r.submitter_institute_2),
+    ]
+    .into_iter()
+    .filter_map(|x| match x {
+        (Some(name), Some(inst)) => Some((name, inst)),
+        _ => None,
     })
+    .collect()
 }
 
+/// A submission without rows; call `add_record` for each row
 impl<'a> From<&Record<'a>> for Submission {
     fn from(record: &Record) -> Self {
-        let r = record.row;
-
-        let mut submitters = Vec::new();
-        if let (Some(name), Some(inst)) = (&r.submitter_name_1, &r.submitter_institute_1) {
-            add_submitter(&mut submitters, name, inst);
-        }
-        if let (Some(name), Some(inst)) = (&r.submitter_name_2, &r.submitter_institute_2) {
-            add_submitter(&mut submitters, name, inst);
-        }
+        let mut typ = vec![format!("{}:Submission", MGEND_ONTOLOGY.prefix)];
+        let label = if is_not_provided(&record.row.submission_id) {
+            typ.push(format!("{}:UnidentifiedSubmission", MGEND_ONTOLOGY.prefix));
+            NOT_PROVIDED.to_string()
+        } else {
+            record.row.submission_id.trim().to_string()
+        };
 
         Submission {
             id: Submission::id(record),
-            typ: format!("{}:Submission", MGEND_ONTOLOGY.prefix),
-            label: Submission::id(record),
+            typ,
+            label,
             submissions: Submissions {
                 typ: vec![
                     format!("{}:Submitters", MGEND_ONTOLOGY.prefix),
                     format!("{}:OrderList", OLO.prefix),
                 ],
-                length: submitters.len(),
-                submitters: submitters
-                    .iter()
-                    .enumerate()
-                    .map(|(i, x)| Slot {
-                        index: i + 1,
-                        item: x.clone(),
-                    })
-                    .collect(),
+                length: 0,
+                submitters: Vec::new(),
             },
-            disease_area: record.row.disease_area_1.clone(),
+            disease_area: Vec::new(),
             case: Vec::new(),
+            association: Vec::new(),
+            source_lines: LineRanges::default(),
+            submitter_lists: Vec::new(),
         }
     }
 }
@@ -115,10 +212,9 @@ impl NameSpaces for Submission {
         vec![
             MGEND_CASE,
             MGEND_ONTOLOGY,
+            MGEND_PERSON,
             MGEND_SUBMISSION,
-            FOAF,
             OLO,
-            ORG,
             PAV,
             RDFS,
         ]
@@ -131,19 +227,28 @@ impl Contexts for Submission {
           "@base": MGEND_SUBMISSION.prefix ,
           "id": "@id",
           "type": "@type",
+          "association": {
+            "@id": "mgendo:association",
+            "@type": "@id"
+          },
           "case": {
             "@id": "mgendo:case",
             "@type": "@id"
           },
-          "disease_area": "mgendo:disease_area",
+          "disease_area": {
+            "@id": "mgendo:disease_area",
+            "@type": "@id"
+          },
           "index": "olo:index",
-          "item": "olo:item",
+          "item": {
+            "@id": "olo:item",
+            "@type": "@id"
+          },
           "label": "rdfs:label",
           "length": "olo:length",
-          "name": "foaf:name",
-          "organization": "org:memberOf",
           "submissions": "pav:providedBy",
           "submitters": "olo:slot",
+          "source_lines": "mgendo:source_lines",
         })
     }
 }
@@ -155,7 +260,9 @@ impl ToTurtle for Submission {
         write!(
             vec,
             "{}:{} a {}",
-            MGEND_SUBMISSION.prefix, self.id, self.typ
+            MGEND_SUBMISSION.prefix,
+            self.id,
+            self.typ.join(", ")
         )?;
         write!(vec, " ;\n  rdfs:label {}", self.string(&self.label))?;
 
@@ -172,33 +279,17 @@ impl ToTurtle for Submission {
             }
 
             write!(vec, "\n      {}:index {}", OLO.prefix, x.index)?;
-            write!(vec, " ;\n      {}:item [", OLO.prefix)?;
-            write!(vec, "\n        a {}:Person", FOAF.prefix)?;
-            write!(
-                vec,
-                " ;\n        {}:name {}",
-                FOAF.prefix,
-                self.string(&x.item.name)
-            )?;
-            write!(vec, " ;\n        {}:memberOf [", ORG.prefix)?;
-            write!(vec, "\n          a {}:Organization", ORG.prefix)?;
-            write!(
-                vec,
-                " ;\n          {}:label {}",
-                RDFS.prefix,
-                self.string(&x.item.organization.label)
-            )?;
-            write!(vec, "\n        ]")?;
-            write!(vec, "\n      ]")?;
+            write!(vec, " ;\n      {}:item {}", OLO.prefix, x.item)?;
             write!(vec, "\n    ]")?;
         }
         write!(vec, "\n  ]")?;
 
-        if let Some(v) = &self.disease_area {
-            if let Ok(v) = serde_json::to_string(v) {
-                write!(vec, " ;\n  {}:disease_area {}", MGEND_ONTOLOGY.prefix, v)?;
-            }
-        }
+        self.write_vec(
+            &mut vec,
+            &self.disease_area,
+            format!("{}:disease_area", MGEND_ONTOLOGY.prefix),
+            |x| x.to_string(),
+        )?;
 
         self.write_vec(
             &mut vec,
@@ -206,6 +297,21 @@ impl ToTurtle for Submission {
             format!("{}:case", MGEND_ONTOLOGY.prefix),
             |x| self.pname(x),
         )?;
+        self.write_vec(
+            &mut vec,
+            &self.association,
+            format!("{}:association", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+
+        if !self.source_lines.is_empty() {
+            write!(
+                vec,
+                " ;\n  {}:source_lines {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(self.source_lines.to_string())
+            )?;
+        }
 
         writeln!(vec, " .")?;
 
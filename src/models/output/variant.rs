@@ -1,13 +1,25 @@
 use crate::format::turtle::ToTurtle;
+use crate::models::annotation::{GeneModels, Transcript};
+use crate::models::chromosome::Chromosome;
+use crate::models::consequence;
+use crate::models::consequence::{Allele, ProteinChange};
 use crate::models::context::Contexts;
-use crate::models::input::Record;
+use crate::models::cytoband::CytoBands;
+use crate::models::input::{ClinicalSignificance, Record};
 use crate::models::name_space::{
-    NameSpace, NameSpaces, FALDO, GVO, HCO, MED2RDF, MGEND_CASE, MGEND_DISEASE, MGEND_GENE,
-    MGEND_ONTOLOGY, MGEND_VARIANT, RDF, SKOS,
+    NameSpace, NameSpaces, ENSEMBL, FALDO, GVO, HCO, MED2RDF, MGEND_ASSOCIATION, MGEND_CASE,
+    MGEND_CYTOBAND, MGEND_DISEASE, MGEND_GENE, MGEND_ONTOLOGY, MGEND_VARIANT, OBO, RDF, REFSEQ,
+    SKOS,
 };
+use crate::models::output::association::Association;
 use crate::models::output::case::Case;
+use crate::models::output::cytoband::CytoBand;
 use crate::models::output::disease::Disease;
-use crate::models::output::gene::Gene;
+use crate::models::output::gene::{Gene, GeneConcordance, SignificanceCount};
+use crate::models::output::provenance::{LineRanges, Provenance};
+use crate::models::output::submission::Submission;
+use crate::models::output::Term;
+use crate::models::reference::Reference;
 use crate::{Assembly, VCFInt};
 use serde::ser::SerializeStruct;
 use serde::{Serialize, Serializer};
@@ -18,6 +30,8 @@ use std::io;
 use std::io::Write;
 use strum::Display;
 
+const NCBI_TAXON_HOMO_SAPIENS: &str = "9606";
+
 #[derive(Debug, Serialize)]
 pub struct Variant {
     id: String,
@@ -31,11 +45,47 @@ pub struct Variant {
     #[serde(skip_serializing_if = "Option::is_none")]
     gene: Option<Vec<String>>,
     case: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    association: Vec<String>,
+    #[serde(skip_serializing_if = "LineRanges::is_empty")]
+    source_lines: LineRanges,
     disease: HashSet<String>,
+    /// Organism whose genome carries the variant, given for the variants of infectious disease
+    /// cases to tell them from variants of the pathogen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    overlapping_gene: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    overlapping_transcript: Vec<String>,
+    #[serde(skip)]
+    overlapping_symbol: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gene_concordance: Option<GeneConcordance>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    consequence: Vec<Consequence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amino_acid_concordant: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cytoband: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cytoband_label: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     info: Vec<Info>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    interpretation: Vec<Interpretation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Consequence {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    transcript: String,
+    consequence_type: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protein_change: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -44,12 +94,137 @@ pub struct Info {
     value: String,
 }
 
+/// Clinical significances submitted for a variant with an allele origin, in the manner of the
+/// aggregate records of ClinVar. Both `submission_count` and `significance_count` count
+/// submissions, not case rows.
+#[derive(Debug, Serialize)]
+pub struct Interpretation {
+    #[serde(rename(serialize = "type"))]
+    typ: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allele_origin: Option<String>,
+    submission_count: usize,
+    significance_count: Vec<SignificanceCount>,
+    interpretation_status: InterpretationStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    aggregate_significance: Option<String>,
+    #[serde(skip)]
+    submission: HashSet<String>,
+    #[serde(skip)]
+    significance: HashSet<ClinicalSignificance>,
+    /// Submissions by clinical significance, to count each submission once per significance
+    #[serde(skip)]
+    submission_significance: HashSet<(String, ClinicalSignificance)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum InterpretationStatus {
+    /// The pathogenic, benign and uncertain significance calls do not disagree
+    Concordant,
+    /// More than one of pathogenic or likely pathogenic, benign or likely benign, and uncertain
+    /// significance is called
+    Conflicting,
+    /// Neither pathogenic, benign nor uncertain significance is called
+    NoClassification,
+}
+
+/// Reason the consequences of a variant are not predicted, as reported in
+/// mgend_report_consequence.tsv
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ReferenceIssue {
+    /// The chromosome is not in the reference genome
+    ReferenceMissingContig,
+    /// The reference allele disagrees with the reference genome
+    ReferenceMismatch,
+}
+
+impl Interpretation {
+    fn new(allele_origin: Option<String>) -> Self {
+        Interpretation {
+            typ: format!("{}:AggregateInterpretation", MGEND_ONTOLOGY.prefix),
+            allele_origin,
+            submission_count: 0,
+            significance_count: Vec::new(),
+            interpretation_status: InterpretationStatus::NoClassification,
+            aggregate_significance: None,
+            submission: HashSet::new(),
+            significance: HashSet::new(),
+            submission_significance: HashSet::new(),
+        }
+    }
+
+    fn add(&mut self, record: &Record) {
+        let significance = &record.row.clinical_significance;
+
+        let submission = Submission::id(record);
+        if self
+            .submission_significance
+            .insert((submission.clone(), significance.clone()))
+        {
+            SignificanceCount::add(&mut self.significance_count, significance.term());
+        }
+        self.submission.insert(submission);
+        self.submission_count = self.submission.len();
+        self.significance.insert(significance.clone());
+
+        let has = |x: ClinicalSignificance| self.significance.contains(&x);
+        let pathogenic = (
+            has(ClinicalSignificance::Pathogenic),
+            has(ClinicalSignificance::LikelyPathogenic),
+        );
+        let benign = (
+            has(ClinicalSignificance::Benign),
+            has(ClinicalSignificance::LikelyBenign),
+        );
+        let uncertain = has(ClinicalSignificance::UncertainSignificance);
+
+        let aggregate =
+            |x: (bool, bool), strong: ClinicalSignificance, weak: ClinicalSignificance| match x {
+                (true, true) => format!("{}:{}{}", MGEND_ONTOLOGY.prefix, strong, weak),
+                (true, false) => strong.term(),
+                _ => weak.term(),
+            };
+
+        let (status, significance) = match (pathogenic, benign, uncertain) {
+            ((false, false), (false, false), false) => {
+                (InterpretationStatus::NoClassification, None)
+            }
+            (p, (false, false), false) => (
+                InterpretationStatus::Concordant,
+                Some(aggregate(
+                    p,
+                    ClinicalSignificance::Pathogenic,
+                    ClinicalSignificance::LikelyPathogenic,
+                )),
+            ),
+            ((false, false), b, false) => (
+                InterpretationStatus::Concordant,
+                Some(aggregate(
+                    b,
+                    ClinicalSignificance::Benign,
+                    ClinicalSignificance::LikelyBenign,
+                )),
+            ),
+            ((false, false), (false, false), true) => (
+                InterpretationStatus::Concordant,
+                Some(ClinicalSignificance::UncertainSignificance.term()),
+            ),
+            _ => (InterpretationStatus::Conflicting, None),
+        };
+        self.interpretation_status = status;
+        self.aggregate_significance = significance;
+    }
+}
+
 impl Variant {
     pub fn id(r: &Record) -> String {
         format!(
             "{}_{}_{}_{}_{}_{}",
             r.assembly,
-            r.row.chr,
+            r.chromosome,
             r.row.start,
             r.row.end,
             r.row.reference.as_ref().unwrap_or(&".".to_string()),
@@ -57,15 +232,219 @@ impl Variant {
         )
     }
 
-    pub fn add_case(&mut self, case: &Case) {
+    /// Link the case and add its clinical significance to the interpretation of its allele origin
+    pub fn add_case(&mut self, case: &Case, record: &Record) {
+        self.source_lines.add(case.provenance());
         self.case
-            .push(format!("{}:{}", MGEND_CASE.prefix, case.identifier()))
+            .push(format!("{}:{}", MGEND_CASE.prefix, case.identifier()));
+        if case.has_infectious_agent() {
+            self.host = Some(format!(
+                "{}:NCBITaxon_{}",
+                OBO.prefix, NCBI_TAXON_HOMO_SAPIENS
+            ));
+        }
+
+        let allele_origin = record.row.allele_origin.as_ref().map(|x| x.term());
+        let i = match self
+            .interpretation
+            .iter()
+            .position(|x| x.allele_origin == allele_origin)
+        {
+            Some(i) => i,
+            None => {
+                self.interpretation.push(Interpretation::new(allele_origin));
+                self.interpretation.len() - 1
+            }
+        };
+        self.interpretation[i].add(record);
+    }
+
+    /// Add the line of a row duplicating a case already added
+    pub fn add_source_line(&mut self, provenance: Option<&Provenance>) {
+        self.source_lines.add(provenance);
+    }
+
+    pub fn add_association(&mut self, association: &Association) {
+        self.source_lines.add(association.provenance());
+        self.association.push(format!(
+            "{}:{}",
+            MGEND_ASSOCIATION.prefix,
+            association.identifier()
+        ));
+    }
+
+    /// Whether the clinical significances of any allele origin conflict
+    pub fn is_conflicting(&self) -> bool {
+        self.interpretation
+            .iter()
+            .any(|x| x.interpretation_status == InterpretationStatus::Conflicting)
     }
 
     pub fn add_disease(&mut self, disease: &Disease) {
         self.disease
             .insert(format!("{}:{}", MGEND_DISEASE.prefix, disease.identifier()));
     }
+
+    /// Annotate with the genes and transcripts overlapping the variant and compare them with the
+    /// submitted genes. Returns the symbols of the overlapping genes.
+    pub fn set_overlap<'m>(&mut self, record: &Record, models: &'m GeneModels) -> Vec<&'m str> {
+        let r = record.row;
+        let genes = models.find(&record.chromosome, r.start, r.end);
+
+        let mut symbols: Vec<&str> = genes.iter().filter_map(|x| x.symbol.as_deref()).collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+
+        self.overlapping_gene = symbols
+            .iter()
+            .map(|x| format!("{}:{}", MGEND_GENE.prefix, Gene::id(x, record.hgnc)))
+            .chain(
+                genes
+                    .iter()
+                    .filter(|x| x.symbol.is_none() && x.id.starts_with("ENS"))
+                    .map(|x| format!("{}:{}", ENSEMBL.prefix, x.id)),
+            )
+            .collect();
+        self.overlapping_transcript = genes
+            .iter()
+            .flat_map(|x| x.transcripts(r.start, r.end))
+            .map(transcript_iri)
+            .collect();
+        self.overlapping_symbol = symbols.iter().map(|x| x.to_string()).collect();
+        self.gene_concordance = GeneConcordance::new(
+            &Gene::parse(record).unwrap_or_default(),
+            &symbols,
+            record.hgnc,
+        );
+
+        symbols
+    }
+
+    /// Symbols of the genes overlapping the variant, if annotated with `set_overlap`
+    pub fn overlapping_symbols(&self) -> impl Iterator<Item = &str> {
+        self.overlapping_symbol.iter().map(|x| x.as_str())
+    }
+
+    pub fn gene_concordance(&self) -> Option<&GeneConcordance> {
+        self.gene_concordance.as_ref()
+    }
+
+    /// Predict the consequences on each overlapping transcript and check them against `AminoAcid`.
+    /// Returns the issue without annotating if the chromosome is not in the reference genome or
+    /// the reference allele disagrees with it.
+    pub fn set_consequence(
+        &mut self,
+        record: &Record,
+        models: &GeneModels,
+        reference: &Reference,
+    ) -> io::Result<Option<ReferenceIssue>> {
+        let r = record.row;
+        let chromosome = record.chromosome.name();
+
+        if !reference.contains(chromosome) {
+            return Ok(Some(ReferenceIssue::ReferenceMissingContig));
+        }
+        if let Some(v) = &r.reference {
+            let end = r.start + v.len() as VCFInt - 1;
+            match reference.fetch(chromosome, r.start, end)? {
+                Some(x) if x.eq_ignore_ascii_case(v.as_bytes()) => {}
+                _ => return Ok(Some(ReferenceIssue::ReferenceMismatch)),
+            }
+        }
+
+        let allele = Allele {
+            chromosome,
+            start: r.start,
+            reference: r.reference.as_deref(),
+            alternate: r.alternate.as_deref(),
+        };
+
+        let mut predicted = Vec::new();
+        for gene in models.find(&record.chromosome, r.start, r.end) {
+            for transcript in gene.transcripts(r.start, r.end) {
+                let prediction = consequence::predict(transcript, &allele, reference)?;
+                if prediction.terms.is_empty() {
+                    continue;
+                }
+                if let Some(ref x) = prediction.protein_change {
+                    predicted.push(x.clone());
+                }
+                self.consequence.push(Consequence {
+                    typ: format!("{}:Consequence", MGEND_ONTOLOGY.prefix),
+                    transcript: transcript_iri(transcript),
+                    consequence_type: prediction
+                        .terms
+                        .iter()
+                        .map(|x| format!("{}:{}", OBO.prefix, x.id()))
+                        .collect(),
+                    protein_change: prediction.protein_change.map(|x| x.to_string()),
+                });
+            }
+        }
+
+        let submitted: Vec<ProteinChange> = r
+            .amino_acid
+            .iter()
+            .flat_map(|x| x.split(','))
+            .filter_map(ProteinChange::parse)
+            .collect();
+        if !submitted.is_empty() && !predicted.is_empty() {
+            self.amino_acid_concordant = Some(
+                submitted
+                    .iter()
+                    .any(|x| predicted.iter().any(|y| x.matches(y))),
+            );
+        }
+
+        Ok(None)
+    }
+
+    pub fn amino_acid_concordant(&self) -> Option<bool> {
+        self.amino_acid_concordant
+    }
+
+    /// Protein changes predicted on any transcript
+    pub fn protein_changes(&self) -> Vec<&str> {
+        self.consequence
+            .iter()
+            .filter_map(|x| x.protein_change.as_deref())
+            .collect()
+    }
+
+    /// Annotate with the bands overlapping the variant, labelled e.g. `17q21.31` or `17q21.31-q21.32`
+    pub fn set_cytoband(&mut self, record: &Record, bands: &CytoBands) {
+        let r = record.row;
+        let chr = record.chromosome.name();
+        let bands = bands.find(&record.chromosome, r.start, r.end);
+
+        self.cytoband = bands
+            .iter()
+            .map(|x| {
+                format!(
+                    "{}:{}",
+                    MGEND_CYTOBAND.prefix,
+                    CytoBand::id(record.assembly, chr, &x.name)
+                )
+            })
+            .collect();
+
+        self.cytoband_label = match (bands.first(), bands.last()) {
+            (Some(first), Some(last)) if bands.len() > 1 => Some(format!(
+                "{}-{}",
+                CytoBand::label(chr, &first.name),
+                last.name
+            )),
+            (Some(first), _) => Some(CytoBand::label(chr, &first.name)),
+            _ => None,
+        };
+    }
+}
+
+fn transcript_iri(transcript: &Transcript) -> String {
+    match transcript.id.starts_with("ENS") {
+        true => format!("{}:{}", ENSEMBL.prefix, transcript.id),
+        false => format!("{}:{}", REFSEQ.prefix, transcript.id),
+    }
 }
 
 impl NameSpaces for Variant {
@@ -84,6 +463,10 @@ impl NameSpaces for Variant {
             MGEND_GENE,
             MGEND_DISEASE,
             MGEND_CASE,
+            MGEND_CYTOBAND,
+            ENSEMBL,
+            REFSEQ,
+            OBO,
         ]
     }
 }
@@ -127,13 +510,41 @@ impl Contexts for Variant {
             "@type": "@id"
           },
           "after": "faldo:after",
+          "aggregate_significance": {
+            "@id": "mgendo:aggregate_significance",
+            "@type": "@id"
+          },
+          "allele_origin": {
+            "@id": "mgendo:allele_origin",
+            "@type": "@id"
+          },
           "alt": "gvo:alt",
+          "amino_acid_concordant": "mgendo:amino_acid_concordant",
           "before": "faldo:before",
           "begin": "faldo:begin",
+          "association": {
+            "@id": "mgendo:association",
+            "@type": "@id"
+          },
           "case": {
             "@id": "mgendo:case",
             "@type": "@id"
           },
+          "case_significance": {
+            "@id": "mgendo:case_significance",
+            "@type": "@id"
+          },
+          "consequence": "mgendo:consequence",
+          "count": "rdf:value",
+          "consequence_type": {
+            "@id": "mgendo:consequence_type",
+            "@type": "@id"
+          },
+          "cytoband": {
+            "@id": "mgendo:cytoband",
+            "@type": "@id"
+          },
+          "cytoband_label": "mgendo:cytoband_label",
           "disease": {
             "@id": "m2r:disease",
             "@type": "@id"
@@ -143,17 +554,40 @@ impl Contexts for Variant {
             "@id": "m2r:gene",
             "@type": "@id"
           },
+          "gene_concordance": "mgendo:gene_concordance",
+          "host": {
+            "@id": "mgendo:host",
+            "@type": "@id"
+          },
           "info": "gvo:info",
+          "interpretation": "mgendo:interpretation",
+          "interpretation_status": "mgendo:interpretation_status",
           "label": "rdfs:label",
           "location": "faldo:location",
           "note": "skos:note",
+          "overlapping_gene": {
+            "@id": "mgendo:overlapping_gene",
+            "@type": "@id"
+          },
+          "overlapping_transcript": {
+            "@id": "mgendo:overlapping_transcript",
+            "@type": "@id"
+          },
           "position": "faldo:position",
+          "protein_change": "mgendo:protein_change",
           "ref": "gvo:ref",
+          "significance_count": "mgendo:significance_count",
+          "submission_count": "mgendo:submission_count",
+          "transcript": {
+            "@id": "mgendo:transcript",
+            "@type": "@id"
+          },
           "reference": {
             "@id": "faldo:reference",
             "@type": "@id"
           },
           "value": "rdf:value",
+          "source_lines": "mgendo:source_lines",
         })
     }
 }
@@ -269,6 +703,12 @@ impl ToTurtle for Variant {
             format!("{}:case", MGEND_ONTOLOGY.prefix),
             |x| self.pname(x),
         )?;
+        self.write_vec(
+            &mut vec,
+            &self.association,
+            format!("{}:association", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
 
         self.write_set(
             &mut vec,
@@ -276,6 +716,78 @@ impl ToTurtle for Variant {
             format!("{}:disease", MED2RDF.prefix),
             |x| self.pname(x),
         )?;
+        if let Some(v) = &self.host {
+            write!(vec, " ;\n  {}:host {}", MGEND_ONTOLOGY.prefix, v)?;
+        }
+
+        self.write_vec(
+            &mut vec,
+            &self.overlapping_gene,
+            format!("{}:overlapping_gene", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        self.write_vec(
+            &mut vec,
+            &self.overlapping_transcript,
+            format!("{}:overlapping_transcript", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        if let Some(v) = &self.gene_concordance {
+            write!(
+                vec,
+                " ;\n  {}:gene_concordance {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(v.to_string())
+            )?;
+        }
+
+        for x in self.consequence.iter() {
+            write!(vec, " ;\n  {}:consequence [", MGEND_ONTOLOGY.prefix)?;
+            write!(vec, "\n    a {}", x.typ)?;
+            write!(
+                vec,
+                " ;\n    {}:transcript {}",
+                MGEND_ONTOLOGY.prefix,
+                self.pname(&x.transcript)
+            )?;
+            write!(
+                vec,
+                " ;\n    {}:consequence_type {}",
+                MGEND_ONTOLOGY.prefix,
+                x.consequence_type.join(", ")
+            )?;
+            if let Some(v) = &x.protein_change {
+                write!(
+                    vec,
+                    " ;\n    {}:protein_change {}",
+                    MGEND_ONTOLOGY.prefix,
+                    self.string(v)
+                )?;
+            }
+            write!(vec, "\n  ]")?;
+        }
+        if let Some(v) = self.amino_acid_concordant {
+            write!(
+                vec,
+                " ;\n  {}:amino_acid_concordant {}",
+                MGEND_ONTOLOGY.prefix, v
+            )?;
+        }
+
+        self.write_vec(
+            &mut vec,
+            &self.cytoband,
+            format!("{}:cytoband", MGEND_ONTOLOGY.prefix),
+            |x| self.pname(x),
+        )?;
+        if let Some(v) = &self.cytoband_label {
+            write!(
+                vec,
+                " ;\n  {}:cytoband_label {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(v)
+            )?;
+        }
 
         if let Some(v) = &self.note {
             self.write_vec(&mut vec, v, format!("{}:note", SKOS.prefix), |x| {
@@ -295,6 +807,65 @@ impl ToTurtle for Variant {
             write!(vec, "\n  ]")?;
         }
 
+        for (i, x) in self.interpretation.iter().enumerate() {
+            if i == 0 {
+                write!(vec, " ;\n  {}:interpretation [", MGEND_ONTOLOGY.prefix)?;
+            } else {
+                write!(vec, " , [")?;
+            }
+            write!(vec, "\n    a {}", x.typ)?;
+            if let Some(v) = &x.allele_origin {
+                write!(vec, " ;\n    {}:allele_origin {}", MGEND_ONTOLOGY.prefix, v)?;
+            }
+            write!(
+                vec,
+                " ;\n    {}:submission_count {}",
+                MGEND_ONTOLOGY.prefix, x.submission_count
+            )?;
+            write!(
+                vec,
+                " ;\n    {}:interpretation_status {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(x.interpretation_status.to_string())
+            )?;
+            if let Some(v) = &x.aggregate_significance {
+                write!(
+                    vec,
+                    " ;\n    {}:aggregate_significance {}",
+                    MGEND_ONTOLOGY.prefix, v
+                )?;
+            }
+            for (j, y) in x.significance_count.iter().enumerate() {
+                if j == 0 {
+                    write!(
+                        vec,
+                        " ;\n    {}:significance_count [",
+                        MGEND_ONTOLOGY.prefix
+                    )?;
+                } else {
+                    write!(vec, " , [")?;
+                }
+                write!(vec, "\n      a {}", y.typ)?;
+                write!(
+                    vec,
+                    " ;\n      {}:case_significance {}",
+                    MGEND_ONTOLOGY.prefix, y.case_significance
+                )?;
+                write!(vec, " ;\n      {}:value {}", RDF.prefix, y.count)?;
+                write!(vec, "\n    ]")?;
+            }
+            write!(vec, "\n  ]")?;
+        }
+
+        if !self.source_lines.is_empty() {
+            write!(
+                vec,
+                " ;\n  {}:source_lines {}",
+                MGEND_ONTOLOGY.prefix,
+                self.string(self.source_lines.to_string())
+            )?;
+        }
+
         writeln!(vec, " .")?;
 
         String::from_utf8(vec)
@@ -335,7 +906,7 @@ impl<'a> From<&Record<'a>> for Variant {
         let (a, r) = (record.assembly, record.row);
 
         let typ = VariantType::new(r.reference.as_ref(), r.alternate.as_ref());
-        let location = Location::new(&typ, &r.chr, a, r.start, r.end);
+        let location = Location::new(&typ, &record.chromosome, a, r.start, r.end);
 
         let mut info = Vec::new();
         if let Some(x) = r.rs.as_ref().and_then(|x| x.strip_prefix("rs")) {
@@ -354,16 +925,30 @@ impl<'a> From<&Record<'a>> for Variant {
             gene: Gene::parse(record).map(|symbols| {
                 symbols
                     .iter()
-                    .map(|symbol| format!("{}:{}", MGEND_GENE.prefix, symbol))
+                    .map(|symbol| {
+                        format!("{}:{}", MGEND_GENE.prefix, Gene::id(symbol, record.hgnc))
+                    })
                     .collect()
             }),
             case: Vec::new(),
+            association: Vec::new(),
+            source_lines: LineRanges::default(),
             disease: HashSet::new(),
+            host: None,
+            overlapping_gene: Vec::new(),
+            overlapping_transcript: Vec::new(),
+            gene_concordance: None,
+            overlapping_symbol: Vec::new(),
+            consequence: Vec::new(),
+            amino_acid_concordant: None,
+            cytoband: Vec::new(),
+            cytoband_label: None,
             note: r
                 .hgvs
                 .as_ref()
                 .map(|x| x.split(",").map(|x| x.trim().to_string()).collect()),
             info,
+            interpretation: Vec::new(),
         }
     }
 }
@@ -376,19 +961,14 @@ enum Location {
 }
 
 impl Location {
-    pub fn new<S: AsRef<str>>(
+    pub fn new(
         typ: &VariantType,
-        chrom: S,
+        chromosome: &Chromosome,
         assembly: &Assembly,
         start: VCFInt,
         end: VCFInt,
     ) -> Self {
-        let mut chr = chrom.as_ref().replace("chr", "");
-        if chr == "M" {
-            chr = "MT".to_string();
-        }
-
-        let reference = format!("{}:{}/{}", HCO.prefix, chr, assembly);
+        let reference = format!("{}:{}/{}", HCO.prefix, chromosome, assembly);
 
         match typ {
             VariantType::SNV => Location::ExactPosition(ExactPosition {
@@ -433,14 +1013,14 @@ impl Serialize for Location {
 }
 
 #[derive(Debug)]
-struct ExactPosition {
-    position: VCFInt,
-    reference: String,
+pub struct ExactPosition {
+    pub position: VCFInt,
+    pub reference: String,
 }
 
-const RDF_TYPE_FALDO_EXACT_POSITION: &'static str = "ExactPosition";
-const RDF_TYPE_FALDO_REGION: &'static str = "Region";
-const RDF_TYPE_FALDO_IN_BETWEEN_POSITION: &'static str = "InBetweenPosition";
+const RDF_TYPE_FALDO_EXACT_POSITION: &str = "ExactPosition";
+const RDF_TYPE_FALDO_REGION: &str = "Region";
+const RDF_TYPE_FALDO_IN_BETWEEN_POSITION: &str = "InBetweenPosition";
 
 impl Serialize for ExactPosition {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -456,9 +1036,9 @@ impl Serialize for ExactPosition {
 }
 
 #[derive(Debug)]
-struct Region {
-    begin: ExactPosition,
-    end: ExactPosition,
+pub struct Region {
+    pub begin: ExactPosition,
+    pub end: ExactPosition,
 }
 
 impl Serialize for Region {
@@ -0,0 +1,101 @@
+use crate::format::turtle::ToTurtle;
+use crate::models::context::Contexts;
+use crate::models::name_space::{NameSpace, NameSpaces, OWL, SKOS};
+use crate::models::output::XRef;
+use crate::models::prefix_map::PrefixMaps;
+use crate::AlternatePredicate;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::io;
+use std::io::Write;
+
+/// IRI of a cross-reference with the IRIs of the alternate prefix maps, written once per IRI
+#[derive(Debug)]
+pub struct XRefNode {
+    id: String,
+    predicate: AlternatePredicate,
+    alternate: Vec<String>,
+}
+
+impl XRefNode {
+    /// `None` if no alternate prefix map gives another IRI
+    pub fn new(xref: &XRef, prefix_maps: &PrefixMaps) -> Option<Self> {
+        let alternate = xref.alternates(prefix_maps);
+
+        (!alternate.is_empty()).then(|| XRefNode {
+            id: xref.iri(prefix_maps),
+            predicate: prefix_maps.predicate.clone(),
+            alternate,
+        })
+    }
+
+    pub fn identifier(&self) -> &String {
+        &self.id
+    }
+}
+
+impl Serialize for XRefNode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("id", &self.id)?;
+        map.serialize_entry(
+            match self.predicate {
+                AlternatePredicate::SameAs => "same_as",
+                AlternatePredicate::ExactMatch => "exact_match",
+            },
+            &self.alternate,
+        )?;
+        map.end()
+    }
+}
+
+impl NameSpaces for XRefNode {
+    fn namespaces() -> Vec<NameSpace> {
+        vec![OWL, SKOS]
+    }
+}
+
+impl Contexts for XRefNode {
+    fn contexts() -> Value {
+        json!({
+          "id": "@id",
+          "exact_match": {
+            "@id": "skos:exactMatch",
+            "@type": "@id"
+          },
+          "same_as": {
+            "@id": "owl:sameAs",
+            "@type": "@id"
+          },
+        })
+    }
+}
+
+impl ToTurtle for XRefNode {
+    fn to_ttl(&self) -> io::Result<String> {
+        let mut vec = Vec::new();
+
+        let predicate = match self.predicate {
+            AlternatePredicate::SameAs => format!("{}:sameAs", OWL.prefix),
+            AlternatePredicate::ExactMatch => format!("{}:exactMatch", SKOS.prefix),
+        };
+        writeln!(
+            vec,
+            "<{}> {} {} .",
+            self.id,
+            predicate,
+            self.alternate
+                .iter()
+                .map(|x| format!("<{}>", x))
+                .collect::<Vec<String>>()
+                .join(" , ")
+        )?;
+
+        String::from_utf8(vec)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)))
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{reader, AlternatePredicate};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// URI formats of the cross-reference prefixes, `$1` standing for the local identifier
+const DEFAULT_URI_FORMATS: [(&str, &str); 9] = [
+    ("icd10", "http://purl.bioontology.org/ontology/ICD10CM/$1"),
+    ("snomedct", "http://identifiers.org/snomedct/$1"),
+    ("mesh", "http://identifiers.org/mesh/$1"),
+    ("medgen", "http://identifiers.org/medgen/$1"),
+    ("medgen.cid", "http://www.ncbi.nlm.nih.gov/medgen/$1"),
+    ("omim", "https://omim.org/entry/$1"),
+    ("omim.ps", "https://omim.org/phenotypicSeries/$1"),
+    ("hp", "http://purl.obolibrary.org/obo/HP_$1"),
+    ("orphanet", "http://purl.obolibrary.org/obo/Orphanet_$1"),
+];
+
+/// Prefix to URI format map in the style of Bioregistry
+#[derive(Debug, Clone, Default)]
+pub struct PrefixMap {
+    formats: HashMap<String, String>,
+}
+
+/// Prefix map of the IRIs of cross-references, and the maps of the alternate IRIs to link them to
+#[derive(Debug, Clone)]
+pub struct PrefixMaps {
+    pub primary: PrefixMap,
+    pub alternates: Vec<PrefixMap>,
+    pub predicate: AlternatePredicate,
+}
+
+impl Default for PrefixMaps {
+    fn default() -> Self {
+        PrefixMaps {
+            primary: PrefixMap::defaults(),
+            alternates: Vec::new(),
+            predicate: AlternatePredicate::ExactMatch,
+        }
+    }
+}
+
+impl PrefixMap {
+    pub fn defaults() -> Self {
+        PrefixMap {
+            formats: DEFAULT_URI_FORMATS
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Read a Bioregistry JSON export (`{"hp": {"uri_format": "..."}}`), a JSON object of URI formats
+    /// (`{"hp": "..."}`) or a TSV of prefixes and URI formats or URI prefixes
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut formats = HashMap::new();
+        let mut reader = reader::open(path.as_ref()).map_err(|e| err(&e))?;
+
+        if reader::extension(path.as_ref()).is_some_and(|x| x == "json") {
+            let mut buf = String::new();
+            reader.read_to_string(&mut buf).map_err(|e| err(&e))?;
+
+            let json: Value = serde_json::from_str(&buf).map_err(|e| err(&e))?;
+            let map = json
+                .as_object()
+                .ok_or_else(|| err(&"expected a JSON object"))?;
+
+            for (prefix, v) in map {
+                let format = match v {
+                    Value::String(x) => Some(x.as_str()),
+                    Value::Object(x) => x.get("uri_format").and_then(|x| x.as_str()),
+                    _ => None,
+                };
+                if let Some(format) = format {
+                    formats.insert(prefix.to_lowercase(), uri_format(format));
+                }
+            }
+        } else {
+            for line in BufReader::new(reader).lines() {
+                let line = line.map_err(|e| err(&e))?;
+                let cols: Vec<&str> = line.split('\t').map(|x| x.trim()).collect();
+
+                match cols.as_slice() {
+                    [prefix, format, ..] if !prefix.starts_with('#') && *prefix != "prefix" => {
+                        formats.insert(prefix.to_lowercase(), uri_format(format));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(PrefixMap { formats })
+    }
+
+    /// Override the URI formats of the prefixes defined in `other`
+    pub fn merge(mut self, other: PrefixMap) -> Self {
+        self.formats.extend(other.formats);
+        self
+    }
+
+    pub fn expand<S: AsRef<str>>(&self, prefix: &str, id: S) -> Option<String> {
+        self.formats
+            .get(prefix)
+            .map(|x| x.replace("$1", id.as_ref()))
+    }
+}
+
+/// A URI prefix like `http://identifiers.org/mesh/` is taken as `http://identifiers.org/mesh/$1`
+fn uri_format(s: &str) -> String {
+    if s.contains("$1") {
+        s.to_string()
+    } else {
+        format!("{}$1", s)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::models::chromosome::Chromosome;
+use crate::{Assembly, VCFInt};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// An entry of a samtools FASTA index (*.fai)
+#[derive(Debug)]
+struct Entry {
+    length: u64,
+    offset: u64,
+    line_bases: u64,
+    line_width: u64,
+}
+
+/// Uncompressed reference genome FASTA with random access by position
+///
+/// Uses `<path>.fai` if present, otherwise indexes the file on load.
+pub struct Reference {
+    file: RefCell<File>,
+    index: HashMap<String, Entry>,
+}
+
+impl Reference {
+    pub fn from_path<P: AsRef<Path>>(path: P, assembly: &Assembly) -> Result<Self, String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let mut fai = PathBuf::from(path.as_ref()).into_os_string();
+        fai.push(".fai");
+
+        let entries = match File::open(&fai) {
+            Ok(f) => read_fai(f).map_err(|e| err(&e))?,
+            Err(_) => {
+                build_fai(File::open(path.as_ref()).map_err(|e| err(&e))?).map_err(|e| err(&e))?
+            }
+        };
+
+        let index = entries
+            .into_iter()
+            .filter_map(|(name, entry)| {
+                Chromosome::new(name, assembly)
+                    .resolved()
+                    .map(|x| (x.to_string(), entry))
+            })
+            .collect();
+
+        Ok(Reference {
+            file: RefCell::new(File::open(path.as_ref()).map_err(|e| err(&e))?),
+            index,
+        })
+    }
+
+    pub fn contains<S: AsRef<str>>(&self, chromosome: S) -> bool {
+        self.index.contains_key(chromosome.as_ref())
+    }
+
+    /// Upper-cased bases of the 1-based closed interval `[start, end]`,
+    /// or `None` if the chromosome is not in the reference or the interval is out of range
+    pub fn fetch<S: AsRef<str>>(
+        &self,
+        chromosome: S,
+        start: VCFInt,
+        end: VCFInt,
+    ) -> io::Result<Option<Vec<u8>>> {
+        let entry = match self.index.get(chromosome.as_ref()) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        if start < 1 || end < start || end as u64 > entry.length || entry.line_bases == 0 {
+            return Ok(None);
+        }
+
+        let (begin, end) = (start as u64 - 1, end as u64);
+        let position =
+            |x: u64| entry.offset + x / entry.line_bases * entry.line_width + x % entry.line_bases;
+
+        let mut buf = vec![0; (position(end - 1) - position(begin) + 1) as usize];
+        let mut file = self.file.borrow_mut();
+        file.seek(SeekFrom::Start(position(begin)))?;
+        file.read_exact(&mut buf)?;
+
+        Ok(Some(
+            buf.into_iter()
+                .filter(|x| !x.is_ascii_whitespace())
+                .map(|x| x.to_ascii_uppercase())
+                .collect(),
+        ))
+    }
+}
+
+fn read_fai(f: File) -> io::Result<Vec<(String, Entry)>> {
+    let invalid =
+        |x: &str| io::Error::new(io::ErrorKind::InvalidData, format!("invalid index: {}", x));
+
+    BufReader::new(f)
+        .lines()
+        .map(|line| {
+            let line = line?;
+            let cols: Vec<&str> = line.split('\t').collect();
+            let num = |i: usize| -> io::Result<u64> {
+                cols.get(i)
+                    .and_then(|x| x.parse().ok())
+                    .ok_or_else(|| invalid(&line))
+            };
+
+            Ok((
+                cols[0].to_string(),
+                Entry {
+                    length: num(1)?,
+                    offset: num(2)?,
+                    line_bases: num(3)?,
+                    line_width: num(4)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn build_fai(f: File) -> io::Result<Vec<(String, Entry)>> {
+    let mut entries: Vec<(String, Entry)> = Vec::new();
+    let mut reader = BufReader::new(f);
+    let mut offset = 0;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line)? as u64;
+        if n == 0 {
+            break;
+        }
+        offset += n;
+
+        if let Some(header) = line.strip_prefix('>') {
+            let name = header.split_whitespace().next().unwrap_or_default();
+            entries.push((
+                name.to_string(),
+                Entry {
+                    length: 0,
+                    offset,
+                    line_bases: 0,
+                    line_width: 0,
+                },
+            ));
+        } else if let Some((_, entry)) = entries.last_mut() {
+            let bases = line.trim_end().len() as u64;
+            if entry.line_bases == 0 {
+                entry.line_bases = bases;
+                entry.line_width = n;
+            }
+            entry.length += bases;
+        }
+    }
+
+    Ok(entries)
+}
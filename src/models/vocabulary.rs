@@ -0,0 +1,133 @@
+use crate::models::ontology;
+use crate::models::output::XRef;
+use crate::models::regex;
+use crate::reader;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct Entry {
+    pub label: String,
+    pub obsolete: bool,
+}
+
+/// Result of looking up a cross-reference in the vocabulary of its code system
+#[derive(Debug, PartialEq)]
+pub enum Validation<'a> {
+    Known(&'a String),
+    Obsolete(&'a String),
+    Unknown,
+}
+
+/// Identifiers and labels of the ICD-10, MeSH, HPO and Orphanet dumps given on the command line
+#[derive(Default)]
+pub struct Vocabularies {
+    systems: HashMap<&'static str, HashMap<String, Entry>>,
+}
+
+/// ICD-10 codes are looked up without the dot, as CMS code lists omit it
+fn key(system: &str, id: &str) -> String {
+    match system {
+        "ICD10" => id.replace('.', "").to_ascii_uppercase(),
+        _ => id.to_string(),
+    }
+}
+
+impl Vocabularies {
+    /// Load an ICD-10 code list with one code and its title per line, e.g. icd10cm_codes_2024.txt
+    pub fn load_icd10<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let entries = self.systems.entry("ICD10").or_default();
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| err(&e))?;
+            if let Some(caps) = regex!(r"^([A-Z]\d[\dA-Z.]*)\s+(.*)$").captures(line.trim()) {
+                entries.insert(
+                    key("ICD10", &caps[1]),
+                    Entry {
+                        label: caps[2].trim().to_string(),
+                        obsolete: false,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load MeSH descriptors or supplementary concepts in the ASCII format, e.g. d2024.bin, c2024.bin
+    pub fn load_mesh<P: AsRef<Path>>(&mut self, path: P) -> Result<(), String> {
+        let err = |e: &dyn std::fmt::Display| format!("{}: {}", path.as_ref().to_string_lossy(), e);
+
+        let entries = self.systems.entry("MeSH").or_default();
+        let reader = BufReader::new(reader::open(path.as_ref()).map_err(|e| err(&e))?);
+
+        let mut label = None;
+        for line in reader.lines() {
+            let line = line.map_err(|e| err(&e))?;
+
+            if line.starts_with("*NEWRECORD") {
+                label = None;
+            } else if let Some(x) = line
+                .strip_prefix("MH = ")
+                .or_else(|| line.strip_prefix("NM = "))
+            {
+                label = Some(x.trim().to_string());
+            } else if let Some(x) = line.strip_prefix("UI = ") {
+                if let Some(label) = label.take() {
+                    entries.insert(
+                        x.trim().to_string(),
+                        Entry {
+                            label,
+                            obsolete: false,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load the terms of an OBO or OWL file, e.g. hp.obo for HPO or ORDO for Orphanet
+    pub fn load_ontology<P: AsRef<Path>>(
+        &mut self,
+        system: &'static str,
+        path: P,
+    ) -> Result<(), String> {
+        let entries = self.systems.entry(system).or_default();
+
+        for term in ontology::read_terms(path)? {
+            // local part of e.g. http://purl.obolibrary.org/obo/HP_0003002
+            if let Some((_, id)) = term.iri.rsplit_once('_') {
+                entries.insert(
+                    id.to_string(),
+                    Entry {
+                        label: term.label,
+                        obsolete: term.obsolete,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// `None` if no vocabulary of the code system is loaded
+    pub fn validate(&self, xref: &XRef) -> Option<Validation<'_>> {
+        let entries = self.systems.get(xref.system())?;
+
+        Some(match entries.get(&key(xref.system(), xref.id())) {
+            Some(x) if x.obsolete => Validation::Obsolete(&x.label),
+            Some(x) => Validation::Known(&x.label),
+            None => Validation::Unknown,
+        })
+    }
+}
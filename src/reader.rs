@@ -0,0 +1,26 @@
+use flate2::bufread::GzDecoder;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Open a plain or gzip-compressed (`*.gz`) file
+pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn io::Read>> {
+    let f = File::open(path.as_ref())?;
+
+    Ok(match path.as_ref().extension() {
+        Some(ext) if ext == "gz" => Box::new(GzDecoder::new(BufReader::new(f))),
+        _ => Box::new(BufReader::new(f)),
+    })
+}
+
+/// Lower-cased extension of a file name, looking past a trailing `.gz`, e.g. `gtf` for `x.gtf.gz`
+pub fn extension<P: AsRef<Path>>(path: P) -> Option<String> {
+    let path = path.as_ref();
+    let path = match path.extension() {
+        Some(ext) if ext == "gz" => Path::new(path.file_stem()?),
+        _ => path,
+    };
+
+    path.extension().map(|x| x.to_string_lossy().to_lowercase())
+}
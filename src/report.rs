@@ -0,0 +1,56 @@
+use csv::WriterBuilder;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Tab-separated list of issues found during conversion, written next to the RDF output
+#[derive(Debug)]
+pub struct Report {
+    filename: &'static str,
+    header: &'static [&'static str],
+    rows: BTreeSet<Vec<String>>,
+}
+
+impl Report {
+    pub fn new(filename: &'static str, header: &'static [&'static str]) -> Self {
+        Report {
+            filename,
+            header,
+            rows: BTreeSet::new(),
+        }
+    }
+
+    pub fn add<S: ToString>(&mut self, row: &[S]) {
+        self.rows
+            .insert(row.iter().map(|x| x.to_string()).collect());
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Write the report if it has any rows
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut out = PathBuf::from(path.as_ref());
+        out.push(self.filename);
+
+        eprintln!("writing {:?} ({} rows)", &out, self.len());
+
+        let mut writer = WriterBuilder::new().delimiter(b'\t').from_path(&out)?;
+        writer.write_record(self.header)?;
+        for row in &self.rows {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}